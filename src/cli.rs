@@ -14,8 +14,16 @@ pub enum Commands {
         root: Option<String>,
     },
     Install {
+        #[arg(help = "Git repo, or a local skill folder or .zip export")]
         repo: String,
-        skill_name: String,
+        #[arg(help = "Skill name, or a glob such as 'doc-*' to install every match")]
+        skill_name: Option<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["skill_name", "alias", "path", "constraint"],
+            help = "Install every skill in the repo"
+        )]
+        all: bool,
         #[arg(long)]
         alias: Option<String>,
         #[arg(long)]
@@ -24,6 +32,17 @@ pub enum Commands {
         root: Option<String>,
         #[arg(long)]
         https: bool,
+        #[arg(
+            long = "ref",
+            help = "Pin to a branch, tag, or commit instead of the default branch"
+        )]
+        git_ref: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "git_ref",
+            help = "Track the highest release tag matching a semver range (e.g. ^2.3)"
+        )]
+        constraint: Option<String>,
     },
     List {
         #[arg(long)]
@@ -48,6 +67,40 @@ pub enum Commands {
         target: String, // installed-name or --all
         #[arg(long)]
         dry_run: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["dry_run", "continue_merge", "abort_merge"],
+            help = "Three-way merge upstream changes into a locally modified skill"
+        )]
+        merge: bool,
+        #[arg(
+            long = "continue",
+            conflicts_with_all = ["dry_run", "abort_merge"],
+            help = "Record the upgrade once merge conflicts are resolved"
+        )]
+        continue_merge: bool,
+        #[arg(
+            long = "abort",
+            conflicts_with = "dry_run",
+            help = "Abandon an in-progress merge and restore the pre-merge files"
+        )]
+        abort_merge: bool,
+    },
+    #[command(
+        about = "Rebuild every locked skill from its locked commit",
+        long_about = "Rebuild every install recorded in skills.lock.json.\n\
+ - Clones or fetches caches that are missing the locked commit.\n\
+ - Extracts each missing skill at its locked commit and verifies the locked digest.\n\
+ - Fails on any digest mismatch; existing installs with local edits are left alone unless --force."
+    )]
+    Restore {
+        #[arg(long)]
+        root: Option<String>,
+        #[arg(
+            long,
+            help = "Replace installs whose contents no longer match the locked digest"
+        )]
+        force: bool,
     },
     Remove {
         installed_name: String,
@@ -55,7 +108,17 @@ pub enum Commands {
         force: bool,
     },
     SyncBack {
-        installed_name: String,
+        #[arg(
+            required_unless_present = "all_modified",
+            help = "Installed skills to publish; skills from the same repo share one branch and PR"
+        )]
+        installed_names: Vec<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["installed_names", "skill_path"],
+            help = "Publish every git-sourced skill whose files differ from the lockfile"
+        )]
+        all_modified: bool,
         #[arg(long)]
         branch: Option<String>,
         #[arg(long)]
@@ -75,6 +138,29 @@ pub enum Commands {
             help = "Use HTTPS when resolving @owner/repo shorthand (default SSH)"
         )]
         https: bool,
+        #[arg(
+            long,
+            help = "Print the diff sync-back would publish, without committing or pushing"
+        )]
+        dry_run: bool,
+        #[arg(
+            long,
+            help = "If the skill changed upstream since it was locked, replay local edits onto the upstream tip instead of refusing"
+        )]
+        rebase: bool,
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["dry_run", "rebase", "branch", "repo", "skill_path"],
+            help = "Write the edits to a format-patch file against the locked commit instead of pushing"
+        )]
+        patch: Option<String>,
+        #[arg(
+            long = "format",
+            requires = "patch",
+            help = "Patch layout: 'patch' (one combined entry, default) or 'mbox' (one entry per skill)"
+        )]
+        patch_format: Option<String>,
     },
     #[command(
         about = "Analyze and repair cache and lockfile",
@@ -113,6 +199,12 @@ pub enum Commands {
             help = "Apply repairs: rebuild missing installs, drop orphan lock entries, prune unreferenced caches, normalize lockfile"
         )]
         apply: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["summary", "status", "diff", "apply", "json"],
+            help = "Re-digest every locked skill so file modes and symlink targets count as modifications"
+        )]
+        migrate_digests: bool,
     },
     Config {
         #[command(subcommand)]
@@ -126,9 +218,67 @@ pub enum Commands {
         #[command(subcommand)]
         cmd: RepoCmd,
     },
+    #[command(
+        about = "Check skills against authoring rules",
+        long_about = "Check skills against authoring rules and exit non-zero on any error.\n\
+ - Front-matter name is well formed and matches the skill directory.\n\
+ - Description is present and within length limits.\n\
+ - Relative links in SKILL.md resolve to bundled files.\n\
+ - Flags oversized files (errors), bundled binaries (warnings), and empty bodies."
+    )]
+    Lint {
+        names: Vec<String>,
+        #[arg(long)]
+        root: Option<String>,
+        #[arg(long)]
+        json: bool,
+    },
+    #[command(
+        about = "Zip an installed skill for upload to Claude.ai or the API",
+        long_about = "Zip an installed skill for upload to Claude.ai or the API.\n\
+ - Validates SKILL.md front-matter (name and description) before writing anything.\n\
+ - The archive holds one top-level folder named after the skill.\n\
+ - Files matched by .skignore are left out, as they are for digests.\n\
+ - Entries are sorted with fixed timestamps, so the same tree always packs to the same bytes."
+    )]
+    Pack {
+        installed_name: String,
+        #[arg(
+            long,
+            help = "Archive path (default: <name>.zip in the current directory)"
+        )]
+        out: Option<String>,
+        #[arg(long)]
+        root: Option<String>,
+    },
+    #[command(about = "Install a skill from a Claude skill zip and record it in the lockfile")]
+    Unpack {
+        archive: String,
+        #[arg(long)]
+        alias: Option<String>,
+        #[arg(long)]
+        root: Option<String>,
+    },
+    #[command(
+        about = "Apply a patch from `sk sync-back --patch` to an installed skill",
+        long_about = "Apply a patch from `sk sync-back --patch` (or plain `git diff` output) to an installed skill.\n\
+ - The patch is applied on top of the installed files, so existing local edits are kept.\n\
+ - Paths outside the skill's folder in its repo are rejected.\n\
+ - On conflict nothing is written and the conflicting files are listed.\n\
+ - The lockfile is not touched; the result shows up as local edits until you sync it back."
+    )]
+    ApplyPatch {
+        installed_name: String,
+        patch_file: String,
+        #[arg(long)]
+        root: Option<String>,
+    },
     #[command(about = "Pre-commit checks (warn on local sources)")]
     Precommit {
-        #[arg(long, help = "Allow local file:// sources without failing")]
+        #[arg(
+            long,
+            help = "Allow local file:// sources and out-of-project imports without failing"
+        )]
         allow_local: bool,
     },
     #[command(about = "Run the repo-scoped MCP skills server over stdio")]
@@ -154,8 +304,28 @@ pub enum CacheCmd {
 
 #[derive(Subcommand, Debug)]
 pub enum ConfigCmd {
-    Get { key: String },
-    Set { key: String, value: String },
+    Get {
+        key: String,
+        #[arg(
+            long,
+            help = "Also print where the value came from (default, user, project .sk.toml, or SK_* env)"
+        )]
+        show_origin: bool,
+    },
+    Set {
+        key: String,
+        value: String,
+        #[arg(
+            long,
+            help = "Write to the repo's committed .sk.toml instead of the user config"
+        )]
+        project: bool,
+    },
+    #[command(about = "Show every effective setting and the layer it came from")]
+    List {
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -1,8 +1,28 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Committed, repo-wide settings. Values here override the user config.
+pub const PROJECT_CONFIG_FILE: &str = ".sk.toml";
+
+/// Prefix for per-key environment overrides (`SK_DEFAULT_ROOT`, `SK_PROTOCOL`, ...).
+/// These win over every file so CI can pin settings without writing config.
+const ENV_PREFIX: &str = "SK_";
+
+/// Every settable key, in display order.
+pub const KEYS: &[&str] = &[
+    "default_root",
+    "protocol",
+    "default_host",
+    "github_user",
+    "default_repo",
+    "template_source",
+    "forge",
+];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -13,6 +33,9 @@ pub struct UserConfig {
     pub github_user: String,
     pub default_repo: String,
     pub template_source: String,
+    /// Which forge opens sync-back PRs: `auto` (detect from the host), a kind
+    /// (`github`, `gitlab`, `gitea`) for every host, or `host=kind` pairs.
+    pub forge: String,
 }
 
 impl Default for UserConfig {
@@ -24,6 +47,148 @@ impl Default for UserConfig {
             github_user: String::new(),
             default_repo: String::new(),
             template_source: default_template_source(),
+            forge: "auto".to_string(),
+        }
+    }
+}
+
+impl UserConfig {
+    pub fn get(&self, key: &str) -> Result<&str> {
+        Ok(match key {
+            "default_root" => &self.default_root,
+            "protocol" => &self.protocol,
+            "default_host" => &self.default_host,
+            "github_user" => &self.github_user,
+            "default_repo" => &self.default_repo,
+            "template_source" => &self.template_source,
+            "forge" => &self.forge,
+            _ => bail!("Unknown key: {key}"),
+        })
+    }
+
+    pub fn set(&mut self, key: &str, value: String) -> Result<()> {
+        let slot = match key {
+            "default_root" => &mut self.default_root,
+            "protocol" => &mut self.protocol,
+            "default_host" => &mut self.default_host,
+            "github_user" => &mut self.github_user,
+            "default_repo" => &mut self.default_repo,
+            "template_source" => &mut self.template_source,
+            "forge" => &mut self.forge,
+            _ => bail!("Unknown key: {key}"),
+        };
+        *slot = value;
+        Ok(())
+    }
+
+    /// Check every key against its schema. `origin_of` names the layer a value
+    /// came from so errors point at the file or variable to fix.
+    fn validate(&self, origin_of: impl Fn(&str) -> String) -> Result<()> {
+        for key in KEYS {
+            let value = self.get(key)?;
+            validate_value(key, value, &self.default_host)
+                .with_context(|| format!("invalid {key} from {}", origin_of(key)))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn env_var_name(key: &str) -> String {
+    format!("{ENV_PREFIX}{}", key.to_ascii_uppercase())
+}
+
+fn ensure_known_key(key: &str, source: &dyn fmt::Display) -> Result<()> {
+    if !KEYS.contains(&key) {
+        bail!(
+            "unknown config key '{key}' in {source} (expected one of: {})",
+            KEYS.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Validate a single value for `key`; `default_host` is needed to expand
+/// `@owner/repo` shorthands.
+pub fn validate_value(key: &str, value: &str, default_host: &str) -> Result<()> {
+    let trimmed = value.trim();
+    match key {
+        "default_root" => {
+            if trimmed.is_empty() {
+                bail!("default_root must not be empty");
+            }
+        }
+        "protocol" => {
+            if !trimmed.eq_ignore_ascii_case("ssh") && !trimmed.eq_ignore_ascii_case("https") {
+                bail!("protocol must be 'ssh' or 'https', got '{value}'");
+            }
+        }
+        "default_host" => {
+            if trimmed.is_empty() || trimmed.contains(['/', ' ']) {
+                bail!("default_host must be a bare host name such as github.com, got '{value}'");
+            }
+        }
+        "github_user" => {
+            if trimmed.contains(['/', ' ', '@']) {
+                bail!("github_user must be a plain user name, got '{value}'");
+            }
+        }
+        "default_repo" => {
+            if !trimmed.is_empty() {
+                let spec = crate::git::parse_repo_input(trimmed, false, default_host)
+                    .with_context(|| format!("default_repo '{value}' is not a repo"))?;
+                if spec.owner.is_empty() || spec.repo.is_empty() || spec.repo.contains('/') {
+                    bail!("default_repo must look like @owner/repo or a git URL, got '{value}'");
+                }
+            }
+        }
+        "template_source" => {
+            let source = crate::template::TemplateSource::parse(trimmed)?;
+            crate::git::parse_repo_input(&source.repo_input, false, default_host).with_context(
+                || format!("template_source repo '{}' is not a repo", source.repo_input),
+            )?;
+        }
+        "forge" => {
+            crate::sync::forge::ForgeSetting::parse(trimmed)?;
+        }
+        _ => bail!("Unknown key: {key}"),
+    }
+    Ok(())
+}
+
+/// Where an effective config value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigOrigin {
+    Default,
+    User(PathBuf),
+    Project(PathBuf),
+    Env(String),
+}
+
+impl fmt::Display for ConfigOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigOrigin::Default => write!(f, "default"),
+            ConfigOrigin::User(path) => write!(f, "user:{}", path.display()),
+            ConfigOrigin::Project(path) => write!(f, "project:{}", path.display()),
+            ConfigOrigin::Env(var) => write!(f, "env:{var}"),
+        }
+    }
+}
+
+/// The effective config plus the layer each key was taken from.
+pub struct LayeredConfig {
+    pub config: UserConfig,
+    origins: HashMap<&'static str, ConfigOrigin>,
+}
+
+impl LayeredConfig {
+    pub fn origin(&self, key: &str) -> &ConfigOrigin {
+        self.origins.get(key).unwrap_or(&ConfigOrigin::Default)
+    }
+
+    fn record(&mut self, key: &str, origin: ConfigOrigin) {
+        if let Some(known) = KEYS.iter().find(|k| **k == key) {
+            self.origins.insert(known, origin);
         }
     }
 }
@@ -44,7 +209,65 @@ pub fn config_path() -> Result<PathBuf> {
     Ok(config_dir()?.join("config.json"))
 }
 
+/// `.sk.toml` at the root of the current git repo, if we are inside one.
+pub fn project_config_path() -> Option<PathBuf> {
+    crate::git::ensure_git_repo()
+        .ok()
+        .map(|root| root.join(PROJECT_CONFIG_FILE))
+}
+
+/// Effective config: built-in defaults, then the user config, then the
+/// project's `.sk.toml`, then `SK_*` environment variables. Unknown keys and
+/// invalid values are rejected, naming the layer they came from.
 pub fn load_or_default() -> Result<UserConfig> {
+    Ok(load_layered()?.config)
+}
+
+pub fn load_layered() -> Result<LayeredConfig> {
+    let mut layered = LayeredConfig {
+        config: UserConfig::default(),
+        origins: HashMap::new(),
+    };
+    let user_path = config_path()?;
+    if user_path.exists() {
+        let data =
+            fs::read(&user_path).with_context(|| format!("reading {}", user_path.display()))?;
+        let raw: serde_json::Map<String, serde_json::Value> = serde_json::from_slice(&data)
+            .with_context(|| format!("parsing {}", user_path.display()))?;
+        for key in raw.keys() {
+            ensure_known_key(key, &user_path.display())?;
+        }
+        layered.config = serde_json::from_value(serde_json::Value::Object(raw.clone()))
+            .with_context(|| format!("parsing {}", user_path.display()))?;
+        for key in raw.keys() {
+            layered.record(key, ConfigOrigin::User(user_path.clone()));
+        }
+    }
+    if let Some(project_path) = project_config_path().filter(|p| p.exists()) {
+        for (key, value) in load_project_table(&project_path)? {
+            ensure_known_key(&key, &project_path.display())?;
+            let Some(value) = value.as_str() else {
+                bail!("{}: '{key}' must be a string", project_path.display());
+            };
+            layered.config.set(&key, value.to_string())?;
+            layered.record(&key, ConfigOrigin::Project(project_path.clone()));
+        }
+    }
+    for key in KEYS {
+        let var = env_var_name(key);
+        if let Ok(value) = std::env::var(&var) {
+            layered.config.set(key, value)?;
+            layered.record(key, ConfigOrigin::Env(var));
+        }
+    }
+    layered
+        .config
+        .validate(|key| layered.origin(key).to_string())?;
+    Ok(layered)
+}
+
+/// The user config alone, for `sk config set` and `sk init` to write back.
+pub fn load_user() -> Result<UserConfig> {
     let path = config_path()?;
     if path.exists() {
         let data = fs::read(&path).with_context(|| format!("reading {}", path.display()))?;
@@ -56,6 +279,45 @@ pub fn load_or_default() -> Result<UserConfig> {
     }
 }
 
+fn load_project_table(path: &Path) -> Result<toml::Table> {
+    let text = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    toml::from_str(&text).with_context(|| format!("parsing {}", path.display()))
+}
+
+/// Validate `value` for `key` before it is written anywhere. A broken layer
+/// elsewhere must not block the fix, so fall back to the default host.
+fn validate_for_write(key: &str, value: &str) -> Result<()> {
+    let host = match key {
+        "default_host" => value.to_string(),
+        _ => load_layered()
+            .map(|layered| layered.config.default_host)
+            .unwrap_or_else(|_| UserConfig::default().default_host),
+    };
+    validate_value(key, value, &host)
+}
+
+/// Set `key` in the user config file.
+pub fn set_user_value(key: &str, value: &str) -> Result<()> {
+    validate_for_write(key, value)?;
+    let mut cfg = load_user()?;
+    cfg.set(key, value.to_string())?;
+    save(&cfg)
+}
+
+/// Set `key` in the project's `.sk.toml`, keeping any other keys it holds.
+pub fn set_project_value(path: &Path, key: &str, value: &str) -> Result<()> {
+    validate_for_write(key, value)?;
+    let mut table = if path.exists() {
+        load_project_table(path)?
+    } else {
+        toml::Table::new()
+    };
+    table.insert(key.to_string(), toml::Value::String(value.to_string()));
+    let text = toml::to_string_pretty(&table)?;
+    fs::write(path, text).with_context(|| format!("writing {}", path.display()))?;
+    Ok(())
+}
+
 pub fn save_if_missing(cfg: &UserConfig) -> Result<()> {
     let path = config_path()?;
     if !path.exists() {
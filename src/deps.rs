@@ -0,0 +1,73 @@
+use crate::skills;
+use std::fs;
+use std::path::Path;
+
+/// A skill directory under the install root whose SKILL.md parses.
+pub struct InstalledSkill {
+    pub install_name: String,
+    pub meta: skills::SkillMeta,
+}
+
+impl InstalledSkill {
+    /// A requirement is satisfied by the install directory name or the
+    /// front-matter name, so aliased installs still count.
+    pub fn provides(&self, requirement: &str) -> bool {
+        self.install_name == requirement || self.meta.name == requirement
+    }
+}
+
+pub fn scan_installed(install_root: &Path) -> Vec<InstalledSkill> {
+    let Ok(entries) = fs::read_dir(install_root) else {
+        return Vec::new();
+    };
+    let mut installed: Vec<InstalledSkill> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let meta = skills::parse_frontmatter_file(&entry.path().join("SKILL.md")).ok()?;
+            Some(InstalledSkill {
+                install_name: entry.file_name().to_string_lossy().to_string(),
+                meta,
+            })
+        })
+        .collect();
+    installed.sort_by(|a, b| a.install_name.cmp(&b.install_name));
+    installed
+}
+
+pub fn is_provided(installed: &[InstalledSkill], requirement: &str) -> bool {
+    installed.iter().any(|skill| skill.provides(requirement))
+}
+
+/// Requirements of `skill` that no installed skill provides.
+pub fn missing_requirements<'a>(
+    installed: &[InstalledSkill],
+    skill: &'a skills::SkillMeta,
+) -> Vec<&'a str> {
+    skill
+        .requires
+        .iter()
+        .map(String::as_str)
+        .filter(|req| !is_provided(installed, req))
+        .collect()
+}
+
+/// Installed skills that would lose a requirement if `install_name` were removed.
+pub fn dependents_of(installed: &[InstalledSkill], install_name: &str) -> Vec<String> {
+    let Some(target) = installed.iter().find(|s| s.install_name == install_name) else {
+        return Vec::new();
+    };
+    let remaining: Vec<&InstalledSkill> = installed
+        .iter()
+        .filter(|s| s.install_name != install_name)
+        .collect();
+    remaining
+        .iter()
+        .filter(|skill| {
+            skill.meta.requires.iter().any(|req| {
+                target.provides(req) && !remaining.iter().any(|other| other.provides(req))
+            })
+        })
+        .map(|skill| skill.install_name.clone())
+        .collect()
+}
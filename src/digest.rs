@@ -1,24 +1,40 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest as _, Sha256};
 use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+/// Lockfiles without a `digestVersion` use the original scheme: relative path
+/// plus CRLF-normalized contents of every regular file.
+pub const LEGACY_DIGEST_VERSION: u32 = 1;
+/// Version 2 hashes the per-file manifest, so file modes (executable bits) and
+/// symlink targets count as well.
+pub const LATEST_DIGEST_VERSION: u32 = 2;
+
+/// Digest `dir` using the scheme named by a lockfile's `digestVersion`.
+pub fn digest_dir_versioned(dir: &Path, version: u32) -> Result<String> {
+    match version {
+        LEGACY_DIGEST_VERSION => digest_dir(dir),
+        LATEST_DIGEST_VERSION => {
+            let mut hasher = Sha256::new();
+            for entry in manifest_dir(dir)? {
+                hasher.update(format!("{} {} {}\n", entry.mode, entry.hash, entry.path));
+            }
+            Ok(format!("sha256:{:x}", hasher.finalize()))
+        }
+        other => bail!("unsupported digestVersion {other}"),
+    }
+}
+
 pub fn digest_dir(dir: &Path) -> Result<String> {
     let mut hasher = Sha256::new();
-    let mut files: Vec<_> = WalkDir::new(dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| !should_ignore(e.path()))
-        .map(|e| e.into_path())
-        .collect();
-    files.sort();
-    for path in files {
-        let rel = path.strip_prefix(dir).unwrap_or(&path);
+    for rel in list_files(dir) {
         hasher.update(rel.to_string_lossy().as_bytes());
-        let data = fs::read(&path)?;
+        let data = fs::read(dir.join(&rel))?;
         let normalized = normalize_crlf(&data);
         hasher.update(&normalized);
     }
@@ -26,6 +42,160 @@ pub fn digest_dir(dir: &Path) -> Result<String> {
     Ok(hex)
 }
 
+/// One file in a skill's per-file manifest. Hashes use the same CRLF
+/// normalization as `digest_dir`; `mode` follows git (`100644`/`100755`, or
+/// `120000` for symlinks, whose hash covers the link target).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileEntry {
+    pub path: String,
+    pub hash: String,
+    pub mode: String,
+}
+
+pub fn manifest_dir(dir: &Path) -> Result<Vec<FileEntry>> {
+    let mut entries = Vec::new();
+    for rel in list_entries(dir, true) {
+        let path = dir.join(&rel);
+        let meta =
+            fs::symlink_metadata(&path).with_context(|| format!("stat {}", path.display()))?;
+        let (hash, mode) = if meta.file_type().is_symlink() {
+            let target =
+                fs::read_link(&path).with_context(|| format!("readlink {}", path.display()))?;
+            let target = target.to_string_lossy().replace('\\', "/");
+            (Sha256::digest(target.as_bytes()), "120000")
+        } else {
+            let data = fs::read(&path).with_context(|| format!("read {}", path.display()))?;
+            (Sha256::digest(normalize_crlf(&data)), file_mode(&meta))
+        };
+        entries.push(FileEntry {
+            path: rel.to_string_lossy().replace('\\', "/"),
+            hash: format!("sha256:{hash:x}"),
+            mode: mode.to_string(),
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(unix)]
+fn file_mode(meta: &fs::Metadata) -> &'static str {
+    use std::os::unix::fs::PermissionsExt;
+    if meta.permissions().mode() & 0o111 != 0 {
+        "100755"
+    } else {
+        "100644"
+    }
+}
+
+#[cfg(not(unix))]
+fn file_mode(_meta: &fs::Metadata) -> &'static str {
+    "100644"
+}
+
+/// Files that differ between a locked manifest and the current tree.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ManifestDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+impl ManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// One `modified: path` / `added: path` / `removed: path` line per change.
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        lines.extend(self.modified.iter().map(|p| format!("modified: {p}")));
+        lines.extend(self.added.iter().map(|p| format!("added: {p}")));
+        lines.extend(self.removed.iter().map(|p| format!("removed: {p}")));
+        lines
+    }
+}
+
+pub fn diff_manifest(locked: &[FileEntry], current: &[FileEntry]) -> ManifestDiff {
+    let before: BTreeMap<&str, &FileEntry> = locked.iter().map(|e| (e.path.as_str(), e)).collect();
+    let after: BTreeMap<&str, &FileEntry> = current.iter().map(|e| (e.path.as_str(), e)).collect();
+    let mut diff = ManifestDiff::default();
+    for (path, entry) in &after {
+        match before.get(path) {
+            None => diff.added.push(path.to_string()),
+            Some(old) if old != entry => diff.modified.push(path.to_string()),
+            Some(_) => {}
+        }
+    }
+    diff.removed = before
+        .keys()
+        .filter(|path| !after.contains_key(*path))
+        .map(|path| path.to_string())
+        .collect();
+    diff
+}
+
+/// Per-skill ignore file (gitignore syntax). Anything it matches is left out of
+/// digests and manifests, sync-back mirroring, and upgrade staging.
+pub const IGNORE_FILE: &str = ".skignore";
+
+/// Walk `dir` without following symlinks, skipping paths matched by any
+/// `.skignore` inside it. Ignore files above `dir` are not consulted.
+pub fn walk_skill_dir(dir: &Path) -> ignore::Walk {
+    WalkBuilder::new(dir)
+        .standard_filters(false)
+        .follow_links(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .build()
+}
+
+/// Regular files under `dir` that count towards its digest, relative to `dir`
+/// and sorted.
+pub fn list_files(dir: &Path) -> Vec<PathBuf> {
+    list_entries(dir, false)
+}
+
+fn list_entries(dir: &Path, include_symlinks: bool) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walk_skill_dir(dir)
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_type()
+                .is_some_and(|t| t.is_file() || (include_symlinks && t.is_symlink()))
+        })
+        .filter(|e| !should_ignore(e.path()))
+        .filter_map(|e| e.path().strip_prefix(dir).ok().map(Path::to_path_buf))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Regular files under `dir` that a `.skignore` excludes, relative to `dir`.
+pub fn ignored_files(dir: &Path) -> Vec<PathBuf> {
+    ignored_entries(dir, false)
+}
+
+/// Like [`ignored_files`], but also lists excluded symlinks.
+pub fn ignored_paths(dir: &Path) -> Vec<PathBuf> {
+    ignored_entries(dir, true)
+}
+
+fn ignored_entries(dir: &Path, include_symlinks: bool) -> Vec<PathBuf> {
+    let kept: HashSet<PathBuf> = walk_skill_dir(dir)
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.path().strip_prefix(dir).ok().map(Path::to_path_buf))
+        .collect();
+    let mut ignored: Vec<PathBuf> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let t = e.file_type();
+            t.is_file() || (include_symlinks && t.is_symlink())
+        })
+        .filter_map(|e| e.path().strip_prefix(dir).ok().map(Path::to_path_buf))
+        .filter(|rel| !kept.contains(rel))
+        .collect();
+    ignored.sort();
+    ignored
+}
+
 fn should_ignore(p: &Path) -> bool {
     let name = p.file_name().and_then(|s| s.to_str()).unwrap_or("");
     name == ".DS_Store" || name.ends_with("~") || name.ends_with(".swp") || name == ".git"
@@ -49,7 +219,7 @@ fn normalize_crlf(input: &[u8]) -> Cow<'_, [u8]> {
     Cow::Owned(out)
 }
 
-fn looks_binary(data: &[u8]) -> bool {
+pub(crate) fn looks_binary(data: &[u8]) -> bool {
     data.contains(&0)
 }
 
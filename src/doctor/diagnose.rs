@@ -1,5 +1,5 @@
 use super::{cache, manifest, report::SkillReport, update};
-use crate::{config, lock, paths};
+use crate::{config, deps, lock, paths};
 use anyhow::Result;
 use chrono::Utc;
 use std::collections::HashSet;
@@ -33,6 +33,7 @@ struct DoctorState {
     lock_path: PathBuf,
     install_root: PathBuf,
     lockfile: lock::Lockfile,
+    installed: Vec<deps::InstalledSkill>,
     referenced_caches: HashSet<PathBuf>,
     orphans_to_drop: HashSet<String>,
     had_issues: bool,
@@ -55,6 +56,7 @@ impl DoctorState {
         Self {
             apply,
             lock_path,
+            installed: deps::scan_installed(&install_root),
             install_root,
             lockfile,
             referenced_caches: HashSet::new(),
@@ -100,10 +102,12 @@ impl DoctorState {
     fn inspect_skill(&mut self, skill: &lock::LockSkill) -> Option<SkillReport> {
         let mut report = SkillReport::default();
         let install_dir = self.install_root.join(&skill.install_name);
-        let spec = skill.source.repo_spec();
-        let cache_dir =
-            paths::resolve_or_primary_cache_path(&spec.url, &spec.host, &spec.owner, &spec.repo);
-        self.referenced_caches.insert(cache_dir.clone());
+        let cache_dir = skill.source.git_spec().map(|spec| {
+            paths::resolve_or_primary_cache_path(&spec.url, &spec.host, &spec.owner, &spec.repo)
+        });
+        if let Some(cache_dir) = &cache_dir {
+            self.referenced_caches.insert(cache_dir.clone());
+        }
 
         let mut local_modified = false;
         if !install_dir.exists() {
@@ -112,16 +116,35 @@ impl DoctorState {
                 install_dir.display()
             ));
             if self.apply {
-                self.rebuild_missing_install(&cache_dir, skill, &install_dir, &mut report);
+                match &cache_dir {
+                    Some(cache_dir) => {
+                        self.rebuild_missing_install(cache_dir, skill, &install_dir, &mut report)
+                    }
+                    None => self.rebuild_missing_local(skill, &install_dir, &mut report),
+                }
             }
         } else {
             if let Err(msg) = manifest::validate_skill_manifest(&install_dir) {
                 report.add_issue(format!("- {msg}"));
             }
-            match crate::digest::digest_dir(&install_dir) {
+            if let Some(installed) = self
+                .installed
+                .iter()
+                .find(|s| s.install_name == skill.install_name)
+            {
+                for dep in deps::missing_requirements(&self.installed, &installed.meta) {
+                    report.add_issue(format!("- Missing dependency: requires '{dep}'"));
+                }
+            }
+            match crate::digest::digest_dir_versioned(&install_dir, self.lockfile.digest_version) {
                 Ok(hash) if hash == skill.digest => {}
                 Ok(_) => {
                     report.add_issue("- Digest mismatch (modified)".to_string());
+                    if let Some(changes) = skill.manifest_changes(&install_dir) {
+                        for line in changes.describe() {
+                            report.add_note(format!("  {line}"));
+                        }
+                    }
                     local_modified = true;
                 }
                 Err(_) => {
@@ -131,16 +154,30 @@ impl DoctorState {
             }
         }
 
-        let upstream_update = update::compute_upstream_update(
-            &cache_dir,
-            spec,
-            &skill.commit,
-            skill.source.skill_path(),
-        );
-        if !cache_dir.exists() {
-            report.add_issue(format!("- Cache clone missing: {}", cache_dir.display()));
-        } else if !crate::git::has_object(&cache_dir, &skill.commit).unwrap_or(false) {
-            report.add_issue("- Locked commit missing from cache (force-push?)".to_string());
+        let mut upstream_update = None;
+        match (&cache_dir, skill.source.git_spec()) {
+            (Some(cache_dir), Some(spec)) => {
+                upstream_update = update::compute_upstream_update(
+                    cache_dir,
+                    spec,
+                    &skill.tracking(),
+                    &skill.commit,
+                );
+                if !cache_dir.exists() {
+                    report.add_issue(format!("- Cache clone missing: {}", cache_dir.display()));
+                } else if !crate::git::has_object(cache_dir, &skill.commit).unwrap_or(false) {
+                    report
+                        .add_issue("- Locked commit missing from cache (force-push?)".to_string());
+                }
+            }
+            _ => {
+                let stored = skill.source.local_path().unwrap_or_default();
+                if !self.origin_path(stored).exists() {
+                    report.add_note(format!(
+                        "- Local source {stored} is gone; the installed copy can no longer be rebuilt."
+                    ));
+                }
+            }
         }
 
         match (local_modified, upstream_update.as_ref()) {
@@ -197,6 +234,45 @@ impl DoctorState {
         }
     }
 
+    fn rebuild_missing_local(
+        &mut self,
+        skill: &lock::LockSkill,
+        install_dir: &Path,
+        report: &mut SkillReport,
+    ) {
+        let origin = self.origin_path(skill.source.local_path().unwrap_or_default());
+        let rebuilt = crate::local::import(&origin, skill.source.skill_path(), install_dir)
+            .and_then(|_| {
+                crate::digest::digest_dir_versioned(install_dir, self.lockfile.digest_version)
+            });
+        match rebuilt {
+            Ok(hash) if hash == skill.digest => {
+                report.add_note("  Rebuilt from local source.".to_string())
+            }
+            Ok(_) => {
+                let _ = std::fs::remove_dir_all(install_dir);
+                report.add_note(format!(
+                    "  Cannot rebuild: {} changed since install.",
+                    origin.display()
+                ));
+            }
+            Err(err) => {
+                let _ = std::fs::remove_dir_all(install_dir);
+                report.add_note(format!("  Rebuild failed: {err}"));
+            }
+        }
+    }
+
+    /// Local sources are stored relative to the project root when they live inside it.
+    fn origin_path(&self, stored: &str) -> PathBuf {
+        let project_root = self
+            .lock_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        crate::local::resolve_origin(&project_root, stored)
+    }
+
     fn print_cache_messages(&mut self) {
         let cache_messages = cache::gather_cache_messages(&self.referenced_caches, self.apply);
         if !cache_messages.is_empty() {
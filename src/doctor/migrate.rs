@@ -0,0 +1,123 @@
+use super::DoctorArgs;
+use crate::{config, digest, git, install, local, lock, paths};
+use anyhow::{bail, Context, Result};
+use tempfile::tempdir;
+
+struct Migrated {
+    install_name: String,
+    digest: String,
+    files: Vec<digest::FileEntry>,
+}
+
+/// Re-digest every locked skill with the latest digest version. Digests are
+/// recomputed from each skill's locked commit (after checking it still matches
+/// the old digest), so local edits never leak into the lockfile. Skills
+/// imported from a directory or zip are re-read from that origin instead.
+pub fn run(args: DoctorArgs) -> Result<()> {
+    if !args.names.is_empty() {
+        bail!("--migrate-digests applies to the whole lockfile; drop the skill names.");
+    }
+    let project_root = git::ensure_git_repo()?;
+    let lock_path = project_root.join("skills.lock.json");
+    if !lock_path.exists() {
+        bail!("no lockfile found");
+    }
+    let lockfile = lock::Lockfile::load(&lock_path)?;
+    let from = lockfile.digest_version;
+    let to = digest::LATEST_DIGEST_VERSION;
+    if from == to {
+        println!("skills.lock.json already uses digestVersion {to}.");
+        return Ok(());
+    }
+
+    let mut migrated = Vec::new();
+    for skill in &lockfile.skills {
+        let entry = redigest_locked_source(&project_root, skill, from, to)
+            .with_context(|| format!("migrating '{}'", skill.install_name))?;
+        migrated.push(entry);
+    }
+
+    lock::edit_lockfile(&lock_path, |lf| {
+        for entry in &migrated {
+            if let Some(skill) = lf
+                .skills
+                .iter_mut()
+                .find(|s| s.install_name == entry.install_name)
+            {
+                skill.digest = entry.digest.clone();
+                skill.files = entry.files.clone();
+            }
+        }
+        lf.digest_version = to;
+        lf.generated_at = chrono::Utc::now().to_rfc3339();
+        Ok(())
+    })?;
+    println!(
+        "Migrated {} skill(s) from digestVersion {from} to {to}.",
+        migrated.len()
+    );
+
+    let cfg = config::load_or_default()?;
+    let install_root_rel = args.root.unwrap_or(&cfg.default_root);
+    let install_root = paths::resolve_project_path(&project_root, install_root_rel);
+    for entry in &migrated {
+        let dest = install_root.join(&entry.install_name);
+        if !dest.exists() {
+            continue;
+        }
+        let current = digest::digest_dir_versioned(&dest, to).ok();
+        if current.as_deref() != Some(entry.digest.as_str()) {
+            println!(
+                "- {}: install differs from its locked commit (file modes, symlinks, or content); run 'sk doctor {}' for details.",
+                entry.install_name, entry.install_name
+            );
+        }
+    }
+    Ok(())
+}
+
+fn redigest_locked_source(
+    project_root: &std::path::Path,
+    skill: &lock::LockSkill,
+    from: u32,
+    to: u32,
+) -> Result<Migrated> {
+    let checkout = tempdir().context("create temporary directory for digest migration")?;
+    let what = match skill.source.local_path() {
+        Some(stored) => {
+            let origin = local::resolve_origin(project_root, stored);
+            local::import(&origin, skill.source.skill_path(), checkout.path())?;
+            "local source"
+        }
+        None => {
+            extract_locked_commit(skill, checkout.path())?;
+            "locked commit"
+        }
+    };
+    if digest::digest_dir_versioned(checkout.path(), from)? != skill.digest {
+        bail!("{what} no longer matches the locked digest; run 'sk doctor' first");
+    }
+    Ok(Migrated {
+        install_name: skill.install_name.clone(),
+        digest: digest::digest_dir_versioned(checkout.path(), to)?,
+        files: digest::manifest_dir(checkout.path())?,
+    })
+}
+
+fn extract_locked_commit(skill: &lock::LockSkill, dest: &std::path::Path) -> Result<()> {
+    let spec = skill.source.repo_spec_owned();
+    let cache_dir =
+        paths::resolve_or_primary_cache_path(&spec.url, &spec.host, &spec.owner, &spec.repo);
+    if !cache_dir.exists() || !git::has_object(&cache_dir, &skill.commit)? {
+        git::ensure_cached_repo(&cache_dir, &spec)?;
+    }
+    if !git::has_object(&cache_dir, &skill.commit)? {
+        bail!(
+            "locked commit {} missing from cache for {}/{}",
+            &skill.commit[..skill.commit.len().min(7)],
+            spec.owner,
+            spec.repo
+        );
+    }
+    install::extract_subdir_from_commit(&cache_dir, &skill.commit, skill.source.skill_path(), dest)
+}
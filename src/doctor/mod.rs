@@ -1,6 +1,7 @@
 mod cache;
 mod diagnose;
 mod manifest;
+mod migrate;
 mod report;
 mod runner;
 mod update;
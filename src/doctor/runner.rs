@@ -1,4 +1,4 @@
-use super::diagnose;
+use super::{diagnose, migrate};
 use crate::{config, digest, git, install, lock, paths, skills};
 use anyhow::{bail, Context, Result};
 use owo_colors::OwoColorize;
@@ -13,6 +13,7 @@ pub enum DoctorMode {
     Summary,
     Status,
     Diff,
+    MigrateDigests,
 }
 
 pub struct DoctorArgs<'a> {
@@ -29,6 +30,7 @@ pub fn run_doctor(args: DoctorArgs) -> Result<()> {
         DoctorMode::Summary => run_summary(args),
         DoctorMode::Status => run_status(args),
         DoctorMode::Diff => run_diff(args),
+        DoctorMode::MigrateDigests => migrate::run(args),
     }
 }
 
@@ -41,7 +43,7 @@ fn run_summary(args: DoctorArgs) -> Result<()> {
     let targets = select_skills(&ctx.lockfile.skills, args.names);
     let entries: Vec<CheckEntry> = targets
         .into_iter()
-        .map(|skill| build_check_entry(&ctx.install_root, skill))
+        .map(|skill| build_check_entry(&ctx.install_root, ctx.lockfile.digest_version, skill))
         .collect();
 
     if args.json {
@@ -59,7 +61,7 @@ fn run_status(args: DoctorArgs) -> Result<()> {
     let targets = select_skills(&ctx.lockfile.skills, args.names);
     let entries: Vec<StatusEntry> = targets
         .into_iter()
-        .map(|skill| build_status_entry(&ctx.install_root, skill))
+        .map(|skill| build_status_entry(&ctx.install_root, ctx.lockfile.digest_version, skill))
         .collect();
 
     if args.json {
@@ -81,14 +83,22 @@ fn run_diff(args: DoctorArgs) -> Result<()> {
     let ctx = load_project_context(args.root)?;
     let targets = select_skills(&ctx.lockfile.skills, args.names);
     ensure_requested_skills_exist(&targets, args.names)?;
-    let repo_tips = resolve_remote_tips_for_targets(&targets);
+    let (local, targets): (Vec<_>, Vec<_>) = targets.into_iter().partition(|s| s.source.is_local());
+    for skill in local {
+        eprintln!(
+            "{}: installed from local source {}; no remote to diff against",
+            skill.install_name,
+            skill.source.local_path().unwrap_or_default()
+        );
+    }
+    let repo_caches = refresh_caches_for_targets(&targets);
     let stdout_is_tty = std::io::stdout().is_terminal();
     let mut printed_any = false;
     for skill in targets {
         printed_any = process_skill_diff(
             &ctx.install_root,
             skill,
-            &repo_tips,
+            &repo_caches,
             stdout_is_tty,
             printed_any,
         )?;
@@ -122,13 +132,19 @@ fn ensure_requested_skills_exist(targets: &[&lock::LockSkill], names: &[String])
 fn process_skill_diff(
     install_root: &Path,
     skill: &lock::LockSkill,
-    repo_tips: &HashMap<RepoKey, Result<RemoteTip, String>>,
+    repo_caches: &HashMap<RepoKey, Result<PathBuf, String>>,
     stdout_is_tty: bool,
     printed_any: bool,
 ) -> Result<bool> {
     let key = build_repo_key(skill);
-    let remote = match repo_tips.get(&key).cloned() {
-        Some(Ok(tip)) => tip,
+    let remote = match repo_caches.get(&key) {
+        Some(Ok(cache_dir)) => match resolve_remote_tip(cache_dir, skill) {
+            Ok(tip) => tip,
+            Err(err) => {
+                eprintln!("{}: {:#}", skill.install_name, err);
+                return Ok(printed_any);
+            }
+        },
         Some(Err(err)) => {
             eprintln!("{}: {}", skill.install_name, err);
             return Ok(printed_any);
@@ -150,10 +166,10 @@ fn process_skill_diff(
             }
             println!("==> {}", display_name);
             println!(
-                "remote: {} @ {} (origin/{})",
+                "remote: {} @ {} ({})",
                 format_repo_id(skill),
                 &remote.commit[..7],
-                remote.branch
+                remote.label
             );
             print!("{diff_text}");
             Ok(true)
@@ -213,14 +229,18 @@ fn select_skills<'a>(skills: &'a [lock::LockSkill], names: &[String]) -> Vec<&'a
         .collect()
 }
 
-fn build_check_entry(install_root: &Path, skill: &lock::LockSkill) -> CheckEntry {
+fn build_check_entry(
+    install_root: &Path,
+    digest_version: u32,
+    skill: &lock::LockSkill,
+) -> CheckEntry {
     let dest = install_root.join(&skill.install_name);
     let state = if !dest.exists() {
         "missing".to_string()
     } else {
         let skill_md = dest.join("SKILL.md");
         let manifest_ok = skill_md.exists() && skills::parse_frontmatter_file(&skill_md).is_ok();
-        let digest_ok = digest::digest_dir(&dest)
+        let digest_ok = digest::digest_dir_versioned(&dest, digest_version)
             .map(|h| h == skill.digest)
             .unwrap_or(false);
         if manifest_ok && digest_ok {
@@ -235,9 +255,13 @@ fn build_check_entry(install_root: &Path, skill: &lock::LockSkill) -> CheckEntry
     }
 }
 
-fn build_status_entry(install_root: &Path, skill: &lock::LockSkill) -> StatusEntry {
+fn build_status_entry(
+    install_root: &Path,
+    digest_version: u32,
+    skill: &lock::LockSkill,
+) -> StatusEntry {
     let dest = install_root.join(&skill.install_name);
-    let (state, current_digest) = compute_install_state(&dest, &skill.digest);
+    let (state, current_digest) = compute_install_state(&dest, digest_version, &skill.digest);
     let update = compute_remote_update(skill);
     StatusEntry {
         install_name: skill.install_name.clone(),
@@ -248,11 +272,15 @@ fn build_status_entry(install_root: &Path, skill: &lock::LockSkill) -> StatusEnt
     }
 }
 
-fn compute_install_state(dir: &Path, expected_digest: &str) -> (String, Option<String>) {
+fn compute_install_state(
+    dir: &Path,
+    digest_version: u32,
+    expected_digest: &str,
+) -> (String, Option<String>) {
     if !dir.exists() {
         return ("missing".to_string(), None);
     }
-    match digest::digest_dir(dir).ok() {
+    match digest::digest_dir_versioned(dir, digest_version).ok() {
         Some(hash) if hash == expected_digest => ("clean".to_string(), Some(hash)),
         Some(hash) => ("modified".to_string(), Some(hash)),
         None => ("modified".to_string(), None),
@@ -260,15 +288,15 @@ fn compute_install_state(dir: &Path, expected_digest: &str) -> (String, Option<S
 }
 
 fn compute_remote_update(skill: &lock::LockSkill) -> Option<String> {
-    let spec = skill.source.repo_spec();
+    let spec = skill.source.git_spec()?;
     let cache_dir =
         paths::resolve_or_primary_cache_path(&spec.url, &spec.host, &spec.owner, &spec.repo);
     if !cache_dir.exists() {
         return None;
     }
-    let owned = skill.source.repo_spec_owned();
-    let branch = git::detect_or_set_default_branch(&cache_dir, &owned).ok()?;
-    let tip = git::rev_parse(&cache_dir, &format!("refs/remotes/origin/{branch}")).ok()?;
+    let tip = git::resolve_tracked_tip(&cache_dir, spec, &skill.tracking())
+        .ok()?
+        .commit;
     if tip == skill.commit {
         None
     } else {
@@ -294,9 +322,9 @@ fn build_repo_key(skill: &lock::LockSkill) -> RepoKey {
     }
 }
 
-fn resolve_remote_tips_for_targets(
+fn refresh_caches_for_targets(
     skills: &[&lock::LockSkill],
-) -> HashMap<RepoKey, Result<RemoteTip, String>> {
+) -> HashMap<RepoKey, Result<PathBuf, String>> {
     let mut uniq: HashSet<RepoKey> = HashSet::new();
     for skill in skills {
         uniq.insert(build_repo_key(skill));
@@ -304,7 +332,7 @@ fn resolve_remote_tips_for_targets(
     let mut handles = Vec::new();
     for key in uniq.into_iter() {
         handles.push(thread::spawn(move || {
-            let result = resolve_remote_tip_for_key(&key).map_err(|err| format!("{err:#}"));
+            let result = refresh_cache_for_key(&key).map_err(|err| format!("{err:#}"));
             (key, result)
         }));
     }
@@ -316,7 +344,7 @@ fn resolve_remote_tips_for_targets(
     map
 }
 
-fn resolve_remote_tip_for_key(key: &RepoKey) -> Result<RemoteTip> {
+fn refresh_cache_for_key(key: &RepoKey) -> Result<PathBuf> {
     let cache_dir =
         paths::resolve_or_primary_cache_path(&key.url, &key.host, &key.owner, &key.repo);
     let spec = git::RepoSpec {
@@ -327,12 +355,16 @@ fn resolve_remote_tip_for_key(key: &RepoKey) -> Result<RemoteTip> {
     };
     git::ensure_cached_repo(&cache_dir, &spec)
         .with_context(|| format!("refreshing cache for {}/{}", spec.owner, spec.repo))?;
-    let branch = git::detect_or_set_default_branch(&cache_dir, &spec)?;
-    let tip = git::rev_parse(&cache_dir, &format!("refs/remotes/origin/{branch}"))?;
+    Ok(cache_dir)
+}
+
+fn resolve_remote_tip(cache_dir: &Path, skill: &lock::LockSkill) -> Result<RemoteTip> {
+    let spec = skill.source.repo_spec_owned();
+    let tip = git::resolve_tracked_tip(cache_dir, &spec, &skill.tracking())?;
     Ok(RemoteTip {
-        cache_dir,
-        branch,
-        commit: tip,
+        cache_dir: cache_dir.to_path_buf(),
+        label: tip.label,
+        commit: tip.commit,
     })
 }
 
@@ -344,7 +376,7 @@ enum DiffOutcome {
 #[derive(Clone)]
 struct RemoteTip {
     cache_dir: PathBuf,
-    branch: String,
+    label: String,
     commit: String,
 }
 
@@ -403,7 +435,9 @@ fn diff_skill(
 }
 
 fn format_repo_id(skill: &lock::LockSkill) -> String {
-    let spec = skill.source.repo_spec();
+    let Some(spec) = skill.source.git_spec() else {
+        return skill.source.local_path().unwrap_or_default().to_string();
+    };
     let base = if spec.host == "local" {
         spec.url.clone()
     } else {
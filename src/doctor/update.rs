@@ -2,13 +2,16 @@ use crate::{git, lock};
 use std::path::Path;
 
 pub fn lock_entry_key(skill: &lock::LockSkill) -> String {
-    let spec = skill.source.repo_spec();
+    let (host, owner, repo) = match skill.source.git_spec() {
+        Some(spec) => (spec.host.as_str(), spec.owner.as_str(), spec.repo.as_str()),
+        None => ("local", "", skill.source.local_path().unwrap_or_default()),
+    };
     format!(
         "{}|{}|{}|{}|{}|{}|{}",
         skill.install_name,
-        spec.host,
-        spec.owner,
-        spec.repo,
+        host,
+        owner,
+        repo,
         skill.source.skill_path(),
         skill.commit,
         skill.digest
@@ -18,19 +21,19 @@ pub fn lock_entry_key(skill: &lock::LockSkill) -> String {
 pub fn compute_upstream_update(
     cache_dir: &Path,
     spec: &git::RepoSpec,
+    tracking: &git::Tracking<'_>,
     current_commit: &str,
-    skill_path: &str,
 ) -> Option<String> {
     if !cache_dir.exists() {
         return None;
     }
-    let branch = git::detect_or_set_default_branch(cache_dir, spec).ok()?;
-    let tip_ref = format!("refs/remotes/origin/{branch}");
-    let new_sha = git::rev_parse(cache_dir, &tip_ref).ok()?;
+    let new_sha = git::resolve_tracked_tip(cache_dir, spec, tracking)
+        .ok()?
+        .commit;
     if new_sha == current_commit {
         None
     } else {
-        let trimmed = skill_path.trim();
+        let trimmed = tracking.skill_path.trim();
         if !trimmed.is_empty() && trimmed != "." {
             let relevant =
                 git::diff_includes_path(cache_dir, current_commit, &new_sha, trimmed).ok()?;
@@ -51,6 +51,26 @@ pub fn ensure_git_repo() -> Result<PathBuf> {
     Ok(PathBuf::from(s.trim()))
 }
 
+/// Absolute path to `name` inside the project's git dir (e.g. `.git/sk-merge`),
+/// honouring worktrees and `$GIT_DIR` the same way `git rev-parse --git-path` does.
+pub fn git_path(project_root: &Path, name: &str) -> Result<PathBuf> {
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(project_root)
+        .args(["rev-parse", "--git-path", name])
+        .output()
+        .context("git rev-parse --git-path failed")?;
+    if !out.status.success() {
+        bail!("unable to locate git dir for {}", project_root.display());
+    }
+    let raw = PathBuf::from(String::from_utf8_lossy(&out.stdout).trim());
+    Ok(if raw.is_absolute() {
+        raw
+    } else {
+        project_root.join(raw)
+    })
+}
+
 pub fn parse_repo_input(input: &str, https: bool, default_host: &str) -> Result<RepoSpec> {
     // Shorthand: @owner/repo -> choose https or ssh on default_host
     if let Some(rest) = input.strip_prefix('@') {
@@ -150,9 +170,15 @@ pub fn ensure_cached_repo(cache_dir: &Path, spec: &RepoSpec) -> Result<()> {
             );
         }
     }
-    // fetch --prune
+    // fetch --prune --tags so installs pinned to a tag can resolve it
     let status = Command::new("git")
-        .args(["-C", &cache_dir.to_string_lossy(), "fetch", "--prune"])
+        .args([
+            "-C",
+            &cache_dir.to_string_lossy(),
+            "fetch",
+            "--prune",
+            "--tags",
+        ])
         .status()
         .context("git fetch failed")?;
     if !status.success() {
@@ -276,6 +302,105 @@ pub fn rev_parse(cache_dir: &Path, rev: &str) -> Result<String> {
     Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
 }
 
+/// What an install follows upstream: a semver constraint over release tags,
+/// a pinned ref, or (when neither is set) the remote default branch.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tracking<'a> {
+    pub git_ref: Option<&'a str>,
+    pub constraint: Option<&'a str>,
+    pub skill_path: &'a str,
+}
+
+/// The commit an install follows upstream, plus a human-readable label for it
+/// (`origin/<branch>`, the pinned ref, or the selected release tag).
+#[derive(Debug, Clone)]
+pub struct TrackedTip {
+    pub commit: String,
+    pub label: String,
+}
+
+/// Resolve the upstream commit for an install. Constraints pick the highest
+/// matching release tag, pinned refs resolve as-is, and everything else follows
+/// the tip of the remote default branch.
+pub fn resolve_tracked_tip(
+    cache_dir: &Path,
+    spec: &RepoSpec,
+    tracking: &Tracking<'_>,
+) -> Result<TrackedTip> {
+    if let Some(raw) = tracking.constraint {
+        let req = crate::versions::parse_constraint(raw)?;
+        let tags = list_tags(cache_dir)?;
+        let picked =
+            crate::versions::select_tag(&tags, &req, tracking.skill_path).ok_or_else(|| {
+                anyhow!(
+                    "no release tag in {}/{} satisfies '{raw}' for '{}'",
+                    spec.owner,
+                    spec.repo,
+                    tracking.skill_path
+                )
+            })?;
+        let commit = rev_parse(cache_dir, &format!("refs/tags/{}^{{commit}}", picked.tag))?;
+        return Ok(TrackedTip {
+            commit,
+            label: picked.tag,
+        });
+    }
+    if let Some(git_ref) = tracking.git_ref {
+        let commit = resolve_ref(cache_dir, git_ref)?;
+        return Ok(TrackedTip {
+            commit,
+            label: git_ref.to_string(),
+        });
+    }
+    let branch = detect_or_set_default_branch(cache_dir, spec)?;
+    let commit = rev_parse(cache_dir, &format!("refs/remotes/origin/{branch}"))?;
+    Ok(TrackedTip {
+        commit,
+        label: format!("origin/{branch}"),
+    })
+}
+
+pub fn list_tags(cache_dir: &Path) -> Result<Vec<String>> {
+    let out = Command::new("git")
+        .args([
+            "-C",
+            &cache_dir.to_string_lossy(),
+            "for-each-ref",
+            "--format=%(refname:strip=2)",
+            "refs/tags",
+        ])
+        .output()
+        .context("git for-each-ref failed")?;
+    if !out.status.success() {
+        bail!("unable to list tags in {}", cache_dir.display());
+    }
+    Ok(String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// Resolve a branch, tag, or commit SHA against a cache clone. Remote branches
+/// win over tags so a pinned branch always follows origin rather than the stale
+/// local branch left behind by the initial clone.
+pub fn resolve_ref(cache_dir: &Path, git_ref: &str) -> Result<String> {
+    let candidates = [
+        format!("refs/remotes/origin/{git_ref}^{{commit}}"),
+        format!("refs/tags/{git_ref}^{{commit}}"),
+        format!("{git_ref}^{{commit}}"),
+    ];
+    for rev in &candidates {
+        if let Ok(commit) = rev_parse(cache_dir, rev) {
+            return Ok(commit);
+        }
+    }
+    bail!(
+        "unable to resolve ref '{git_ref}' (not a branch, tag, or commit in {})",
+        cache_dir.display()
+    );
+}
+
 pub fn has_object(cache_dir: &Path, oid: &str) -> Result<bool> {
     let out = Command::new("git")
         .args(["-C", &cache_dir.to_string_lossy(), "cat-file", "-t", oid])
@@ -284,6 +409,24 @@ pub fn has_object(cache_dir: &Path, oid: &str) -> Result<bool> {
     Ok(out.status.success())
 }
 
+/// Whether `ancestor` is reachable from `descendant` (true when they are equal).
+pub fn is_ancestor(cache_dir: &Path, ancestor: &str, descendant: &str) -> Result<bool> {
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(cache_dir)
+        .args(["merge-base", "--is-ancestor", ancestor, descendant])
+        .output()
+        .context("git merge-base --is-ancestor failed")?;
+    match out.status.code() {
+        Some(0) => Ok(true),
+        Some(1) => Ok(false),
+        _ => bail!(
+            "git merge-base --is-ancestor {ancestor} {descendant} failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        ),
+    }
+}
+
 pub fn diff_includes_path(
     cache_dir: &Path,
     base: &str,
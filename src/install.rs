@@ -1,17 +1,59 @@
-use crate::{config, digest, git, lock, paths, skills};
+use crate::{config, deps, digest, git, local, lock, paths, search, skills};
 use anyhow::{bail, Context, Result};
 use chrono::Utc;
+use std::collections::VecDeque;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use tempfile::TempDir;
 
 pub struct InstallArgs<'a> {
     pub repo: &'a str,
-    pub skill_name: &'a str,
+    /// A skill name, or a glob such as `doc-*`; `None` with `all`.
+    pub skill_name: Option<&'a str>,
+    pub all: bool,
     pub alias: Option<&'a str>,
     pub path: Option<&'a str>,
     pub root: Option<&'a str>,
     pub https: bool,
+    pub git_ref: Option<&'a str>,
+    pub constraint: Option<&'a str>,
+}
+
+enum Selector<'a> {
+    Name(&'a str),
+    Pattern(&'a str, globset::GlobMatcher),
+    All,
+}
+
+impl Selector<'_> {
+    fn parse<'a>(args: &InstallArgs<'a>) -> Result<Selector<'a>> {
+        let selector = match (args.skill_name, args.all) {
+            (_, true) => Selector::All,
+            (Some(name), false) if name.contains(['*', '?', '[']) => {
+                let matcher = globset::Glob::new(name)
+                    .with_context(|| format!("invalid skill pattern '{name}'"))?
+                    .compile_matcher();
+                Selector::Pattern(name, matcher)
+            }
+            (Some(name), false) => Selector::Name(name),
+            (None, false) => bail!("Specify a skill name, a pattern such as 'doc-*', or --all."),
+        };
+        if !matches!(selector, Selector::Name(_))
+            && (args.alias.is_some() || args.path.is_some() || args.constraint.is_some())
+        {
+            bail!("--alias, --path, and --constraint apply to a single skill; drop them when installing several.");
+        }
+        Ok(selector)
+    }
+
+    fn matches(&self, skill: &skills::DiscoveredSkill) -> bool {
+        match self {
+            Selector::Name(name) => skill.meta.name == *name,
+            Selector::Pattern(_, matcher) => matcher.is_match(&skill.meta.name),
+            Selector::All => true,
+        }
+    }
 }
 
 pub fn run_install(args: InstallArgs) -> Result<()> {
@@ -19,74 +61,453 @@ pub fn run_install(args: InstallArgs) -> Result<()> {
     let cfg = config::load_or_default()?;
     let root_rel = args.root.unwrap_or(&cfg.default_root);
     let install_root = paths::resolve_project_path(&project_root, root_rel);
+    let lock_path = project_root.join("skills.lock.json");
+    let (requested, same_repo_skills, source_label) = if local::is_local_input(args.repo) {
+        plan_local_install(&project_root, &args)?
+    } else {
+        plan_git_install(&cfg, &args)?
+    };
     fs::create_dir_all(&install_root)?;
 
-    // Parse repo and ensure cache
+    let lockfile = lock::Lockfile::load_or_empty(&lock_path)?;
+    let plan = resolve_requirements(requested, &same_repo_skills, &install_root, &lockfile)?;
+    check_plan(&plan, &install_root, &lockfile)?;
+
+    apply_plan(&project_root, &lock_path, &install_root, &plan)?;
+    if plan.len() > 1 {
+        println!("Installed {} skill(s) from {source_label}.", plan.len());
+    }
+    Ok(())
+}
+
+/// A skill folder or zip export: exactly one skill, no refs or patterns.
+fn plan_local_install(
+    project_root: &Path,
+    args: &InstallArgs,
+) -> Result<(Vec<PlannedInstall>, Vec<skills::DiscoveredSkill>, String)> {
+    if args.all || args.path.is_some() || args.git_ref.is_some() || args.constraint.is_some() {
+        bail!("--all, --path, --ref, and --constraint need a git repo; a local folder or zip holds a single skill.");
+    }
+    let path = Path::new(args.repo).to_path_buf();
+    let unpacked = local::unpack(&path)?;
+    let skill = local::discover(unpacked.root())?;
+    if let Some(requested) = args.skill_name {
+        ensure_skill_name_matches(&skill.meta.name, requested, &skill.skill_path)?;
+    }
+    let stored = local::stored_origin(project_root, &path)?;
+    let planned = PlannedInstall {
+        install_name: args.alias.unwrap_or(&skill.meta.name).to_string(),
+        required_by: None,
+        origin: Origin::Local {
+            stored: stored.clone(),
+            path,
+        },
+        skill,
+        git_ref: None,
+        constraint: None,
+    };
+    Ok((vec![planned], Vec::new(), stored))
+}
+
+fn plan_git_install(
+    cfg: &config::UserConfig,
+    args: &InstallArgs,
+) -> Result<(Vec<PlannedInstall>, Vec<skills::DiscoveredSkill>, String)> {
+    let selector = Selector::parse(args)?;
+
+    // Parse repo and ensure cache (once, however many skills are selected)
     let spec = git::parse_repo_input(args.repo, args.https, &cfg.default_host)?;
     let cache_dir =
         paths::resolve_or_primary_cache_path(&spec.url, &spec.host, &spec.owner, &spec.repo);
     git::ensure_cached_repo(&cache_dir, &spec)?;
-    let default_branch = git::detect_or_set_default_branch(&cache_dir, &spec)?;
 
-    // Resolve commit from the remote default branch
-    let commit = {
-        let rev = format!("refs/remotes/origin/{default_branch}");
-        git::rev_parse(&cache_dir, &rev)?
+    // Resolve commit from the version constraint, the pinned ref, or the remote default branch
+    let skill_path_hint = match (args.constraint, &selector) {
+        (Some(_), Selector::Name(name)) => locate_skill_path(&cache_dir, &spec, args, name)?,
+        _ => String::new(),
     };
+    let tracking = git::Tracking {
+        git_ref: args.git_ref,
+        constraint: args.constraint,
+        skill_path: &skill_path_hint,
+    };
+    let commit = git::resolve_tracked_tip(&cache_dir, &spec, &tracking)?.commit;
 
     // Discover skills
     let skills_found = skills::list_skills_in_repo(&cache_dir, &commit)?;
-    let chosen = if let Some(path_flag) = args.path {
-        let normalized = normalize_skill_subdir(path_flag);
-        pick_skill_by_path(
-            &skills_found,
-            &cache_dir,
-            &commit,
-            &normalized,
-            args.skill_name,
-        )?
-    } else {
-        pick_skill_by_name(&skills_found, args.skill_name, &spec)?
+    let chosen: Vec<skills::DiscoveredSkill> = match &selector {
+        Selector::Name(name) => match args.path {
+            Some(path_flag) => {
+                let normalized = normalize_skill_subdir(path_flag);
+                vec![pick_skill_by_path(
+                    &skills_found,
+                    &cache_dir,
+                    &commit,
+                    &normalized,
+                    name,
+                )?]
+            }
+            None => vec![pick_skill_by_name(&skills_found, name, &spec)?],
+        },
+        Selector::Pattern(pattern, _) => {
+            let matched: Vec<_> = skills_found
+                .iter()
+                .filter(|s| selector.matches(s))
+                .cloned()
+                .collect();
+            if matched.is_empty() {
+                bail!(
+                    "No skills matching '{pattern}' found in {}",
+                    repo_identifier(&spec)
+                );
+            }
+            matched
+        }
+        Selector::All => {
+            if skills_found.is_empty() {
+                bail!("No skills found in {}", repo_identifier(&spec));
+            }
+            skills_found.clone()
+        }
     };
 
-    let install_name = args.alias.unwrap_or(&chosen.meta.name);
-    let dest = install_root.join(install_name);
-    if dest.exists() {
-        let dest_s = dest.display().to_string();
-        bail!("Install destination '{dest_s}' already exists");
+    let requested: Vec<PlannedInstall> = chosen
+        .into_iter()
+        .map(|skill| PlannedInstall {
+            install_name: args.alias.unwrap_or(&skill.meta.name).to_string(),
+            required_by: None,
+            origin: Origin::Git {
+                spec: spec.clone(),
+                cache_dir: cache_dir.clone(),
+                commit: commit.clone(),
+            },
+            skill,
+            git_ref: args.git_ref.map(|r| r.to_string()),
+            constraint: args.constraint.map(|c| c.trim().to_string()),
+        })
+        .collect();
+    Ok((requested, skills_found, repo_identifier(&spec)))
+}
+
+struct PlannedInstall {
+    install_name: String,
+    required_by: Option<String>,
+    origin: Origin,
+    skill: skills::DiscoveredSkill,
+    git_ref: Option<String>,
+    constraint: Option<String>,
+}
+
+enum Origin {
+    Git {
+        spec: git::RepoSpec,
+        cache_dir: PathBuf,
+        commit: String,
+    },
+    Local {
+        /// Path as recorded in the lockfile.
+        stored: String,
+        path: PathBuf,
+    },
+}
+
+/// Refuse the whole plan if any skill can't be installed, listing every reason.
+fn check_plan(
+    plan: &[PlannedInstall],
+    install_root: &Path,
+    lockfile: &lock::Lockfile,
+) -> Result<()> {
+    let mut failures = Vec::new();
+    for (idx, item) in plan.iter().enumerate() {
+        let name = &item.install_name;
+        let dest = install_root.join(name);
+        if plan[..idx].iter().any(|p| &p.install_name == name) {
+            let paths: Vec<&str> = plan
+                .iter()
+                .filter(|p| &p.install_name == name)
+                .map(|p| p.skill.skill_path.as_str())
+                .collect();
+            failures.push(format!(
+                "Multiple skills named '{name}' selected (paths: {}). Install them one at a time with --path and --alias.",
+                paths.join(", ")
+            ));
+        } else if dest.exists() {
+            let dest_s = dest.display().to_string();
+            failures.push(format!("Install destination '{dest_s}' already exists"));
+        } else if lockfile.skills.iter().any(|s| &s.install_name == name) {
+            failures.push(format!(
+                "Lockfile already contains skill with installName '{name}'"
+            ));
+        }
+    }
+    match failures.len() {
+        0 => Ok(()),
+        1 if plan.len() == 1 => bail!("{}", failures.remove(0)),
+        n => {
+            for failure in &failures {
+                eprintln!("- {failure}");
+            }
+            bail!(
+                "{n} of {} skill(s) cannot be installed; nothing was installed.",
+                plan.len()
+            )
+        }
     }
+}
 
-    // Extract subdir from commit to dest via git archive | tar
-    extract_subdir_from_commit(&cache_dir, &commit, &chosen.skill_path, &dest)?;
+/// Extract every planned skill into a staging dir, then move them into place
+/// and record them in a single lockfile edit. A failed extraction leaves the
+/// install root untouched.
+fn apply_plan(
+    project_root: &Path,
+    lock_path: &Path,
+    install_root: &Path,
+    plan: &[PlannedInstall],
+) -> Result<()> {
+    let staging = TempDir::new_in(project_root).context("create staging dir")?;
+    for item in plan {
+        let staged = staging.path().join(&item.install_name);
+        match &item.origin {
+            // Extract subdir from commit via git archive | tar
+            Origin::Git {
+                cache_dir, commit, ..
+            } => extract_subdir_from_commit(cache_dir, commit, &item.skill.skill_path, &staged),
+            Origin::Local { path, .. } => local::import(path, &item.skill.skill_path, &staged),
+        }
+        .with_context(|| format!("extracting '{}'", item.install_name))?;
+    }
 
-    // Compute digest
-    let digest = digest::digest_dir(&dest)?;
+    let mut moved: Vec<PathBuf> = Vec::new();
+    for item in plan {
+        let staged = staging.path().join(&item.install_name);
+        let dest = install_root.join(&item.install_name);
+        if let Err(err) = fs::rename(&staged, &dest) {
+            for done in &moved {
+                let _ = fs::remove_dir_all(done);
+            }
+            return Err(err)
+                .with_context(|| format!("rename {} -> {}", staged.display(), dest.display()));
+        }
+        moved.push(dest);
+    }
 
-    // Update lockfile
-    let lock_path = project_root.join("skills.lock.json");
-    let entry = lock::LockSkill {
-        install_name: install_name.to_string(),
-        source: lock::Source::new(spec.clone(), chosen.skill_path.clone()),
-        legacy_ref: None,
-        commit: commit.clone(),
-        digest: digest.clone(),
-        installed_at: Utc::now().to_rfc3339(),
-    };
-    lock::edit_lockfile(&lock_path, |lf| {
-        if lf.skills.iter().any(|s| s.install_name == install_name) {
-            bail!("Lockfile already contains skill with installName '{install_name}'");
+    // Update lockfile with the digests (in the lockfile's digest version) and per-file manifests
+    let recorded = lock::edit_lockfile(lock_path, |lf| {
+        for item in plan {
+            let dest = install_root.join(&item.install_name);
+            let skill_path = item.skill.skill_path.clone();
+            let (source, commit) = match &item.origin {
+                Origin::Git { spec, commit, .. } => {
+                    lf.ensure_repo_entry(spec);
+                    (lock::Source::new(spec.clone(), skill_path), commit.clone())
+                }
+                Origin::Local { stored, .. } => (
+                    lock::Source::new_local(stored.clone(), skill_path),
+                    String::new(),
+                ),
+            };
+            let entry = lock::LockSkill {
+                install_name: item.install_name.clone(),
+                source,
+                git_ref: item.git_ref.clone(),
+                constraint: item.constraint.clone(),
+                commit,
+                digest: digest::digest_dir_versioned(&dest, lf.digest_version)?,
+                files: digest::manifest_dir(&dest)?,
+                installed_at: Utc::now().to_rfc3339(),
+            };
+            lf.skills.push(entry);
         }
-        lf.ensure_repo_entry(&spec);
-        lf.skills.push(entry.clone());
         lf.generated_at = Utc::now().to_rfc3339();
         Ok(())
-    })?;
+    });
+    if let Err(err) = recorded {
+        for done in &moved {
+            let _ = fs::remove_dir_all(done);
+        }
+        return Err(err);
+    }
 
-    let dest_s = dest.display().to_string();
-    println!("Installed '{install_name}' to {dest_s} @ {}", &commit[..7]);
+    for item in plan {
+        let install_name = &item.install_name;
+        let dest_s = install_root.join(install_name).display().to_string();
+        let at = match &item.origin {
+            Origin::Git { commit, .. } => format!("@ {}", &commit[..7]),
+            Origin::Local { stored, .. } => format!("from {stored}"),
+        };
+        match &item.required_by {
+            Some(parent) => {
+                println!("Installed '{install_name}' (required by '{parent}') to {dest_s} {at}")
+            }
+            None => println!("Installed '{install_name}' to {dest_s} {at}"),
+        }
+    }
     Ok(())
 }
 
+/// Expand the requested installs with every `requires:` entry not already
+/// installed, looking first in the same repo at the same commit and then at the
+/// default branch of each registered repo. Nothing is extracted until the whole
+/// set resolves.
+fn resolve_requirements(
+    requested: Vec<PlannedInstall>,
+    same_repo_skills: &[skills::DiscoveredSkill],
+    install_root: &Path,
+    lockfile: &lock::Lockfile,
+) -> Result<Vec<PlannedInstall>> {
+    let installed = deps::scan_installed(install_root);
+    let mut queue: VecDeque<(String, String)> = requested
+        .iter()
+        .flat_map(|item| {
+            item.skill
+                .meta
+                .requires
+                .iter()
+                .map(|dep| (item.install_name.clone(), dep.clone()))
+        })
+        .collect();
+    let mut registered: Option<Vec<RegisteredRepo>> = None;
+    let mut plan = requested;
+
+    while let Some((parent, dep)) = queue.pop_front() {
+        let planned = plan
+            .iter()
+            .any(|p| p.install_name == dep || p.skill.meta.name == dep);
+        if planned || deps::is_provided(&installed, &dep) {
+            continue;
+        }
+        let same_repo = match &plan[0].origin {
+            Origin::Git {
+                spec,
+                cache_dir,
+                commit,
+            } => find_by_name(same_repo_skills, &dep, spec)?.map(|skill| {
+                let origin = Origin::Git {
+                    spec: spec.clone(),
+                    cache_dir: cache_dir.clone(),
+                    commit: commit.clone(),
+                };
+                (origin, skill)
+            }),
+            Origin::Local { .. } => None,
+        };
+        let found = match same_repo {
+            Some(hit) => Some(hit),
+            None => {
+                let repos = match registered.as_mut() {
+                    Some(repos) => repos,
+                    None => registered.insert(load_registered_repos(lockfile, &plan[0].origin)?),
+                };
+                let mut hit = None;
+                for repo in repos.iter() {
+                    if let Some(skill) = find_by_name(&repo.skills, &dep, &repo.spec)? {
+                        let origin = Origin::Git {
+                            spec: repo.spec.clone(),
+                            cache_dir: repo.cache_dir.clone(),
+                            commit: repo.commit.clone(),
+                        };
+                        hit = Some((origin, skill));
+                        break;
+                    }
+                }
+                hit
+            }
+        };
+        let Some((origin, skill)) = found else {
+            let searched = match &plan[0].origin {
+                Origin::Git { spec, .. } => {
+                    format!("{} or any registered repo", repo_identifier(spec))
+                }
+                Origin::Local { .. } => "any registered repo".to_string(),
+            };
+            bail!(
+                "'{parent}' requires '{dep}', which was not found in {searched}. Install it first or register its repo with 'sk repo add'."
+            );
+        };
+        queue.extend(
+            skill
+                .meta
+                .requires
+                .iter()
+                .map(|next| (dep.clone(), next.clone())),
+        );
+        plan.push(PlannedInstall {
+            install_name: dep,
+            required_by: Some(parent),
+            origin,
+            skill,
+            git_ref: None,
+            constraint: None,
+        });
+    }
+    Ok(plan)
+}
+
+struct RegisteredRepo {
+    spec: git::RepoSpec,
+    cache_dir: PathBuf,
+    commit: String,
+    skills: Vec<skills::DiscoveredSkill>,
+}
+
+fn load_registered_repos(
+    lockfile: &lock::Lockfile,
+    origin: &Origin,
+) -> Result<Vec<RegisteredRepo>> {
+    let origin_key = match origin {
+        Origin::Git { spec, .. } => Some(lock::repo_key(spec)),
+        Origin::Local { .. } => None,
+    };
+    let mut repos = Vec::new();
+    for entry in &lockfile.repos.entries {
+        if origin_key.as_deref() == Some(entry.key.as_str()) {
+            continue;
+        }
+        let spec = entry.spec.clone();
+        let cache_dir =
+            paths::resolve_or_primary_cache_path(&spec.url, &spec.host, &spec.owner, &spec.repo);
+        git::ensure_cached_repo(&cache_dir, &spec)?;
+        let commit = git::resolve_tracked_tip(&cache_dir, &spec, &git::Tracking::default())?.commit;
+        let skills = skills::list_skills_in_repo(&cache_dir, &commit)?;
+        repos.push(RegisteredRepo {
+            spec,
+            cache_dir,
+            commit,
+            skills,
+        });
+    }
+    Ok(repos)
+}
+
+fn find_by_name(
+    skills_found: &[skills::DiscoveredSkill],
+    name: &str,
+    spec: &git::RepoSpec,
+) -> Result<Option<skills::DiscoveredSkill>> {
+    if skills_found.iter().any(|s| s.meta.name == name) {
+        pick_skill_by_name(skills_found, name, spec).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Version constraints select tags scoped to the skill's directory, so the path
+/// must be known before the commit is. Prefer `--path`; otherwise find the skill
+/// by name on the default branch.
+fn locate_skill_path(
+    cache_dir: &Path,
+    spec: &git::RepoSpec,
+    args: &InstallArgs,
+    skill_name: &str,
+) -> Result<String> {
+    if let Some(path_flag) = args.path {
+        return Ok(normalize_skill_subdir(path_flag));
+    }
+    let tip = git::resolve_tracked_tip(cache_dir, spec, &git::Tracking::default())?;
+    let skills_found = skills::list_skills_in_repo(cache_dir, &tip.commit)?;
+    Ok(pick_skill_by_name(&skills_found, skill_name, spec)?.skill_path)
+}
+
 fn pick_skill_by_name(
     skills_found: &[skills::DiscoveredSkill],
     requested_name: &str,
@@ -99,8 +520,12 @@ fn pick_skill_by_name(
         .collect();
     if candidates.is_empty() {
         bail!(
-            "No skill named '{requested_name}' found in {}",
-            repo_identifier(spec)
+            "No skill named '{requested_name}' found in {}.{}",
+            repo_identifier(spec),
+            search::did_you_mean(
+                requested_name,
+                skills_found.iter().map(|s| s.meta.name.as_str())
+            )
         );
     }
     if candidates.len() > 1 {
@@ -1,15 +1,23 @@
 pub mod cli;
 pub mod config;
+pub mod deps;
 pub mod digest;
 pub mod doctor;
 pub mod git;
 pub mod install;
+pub mod lint;
+pub mod local;
 pub mod lock;
 pub mod mcp;
+pub mod pack;
 pub mod paths;
 pub mod remove;
 pub mod repo;
+pub mod restore;
+pub mod search;
 pub mod skills;
+pub mod sync;
 pub mod template;
 pub mod update;
 pub mod upgrade;
+pub mod versions;
@@ -0,0 +1,296 @@
+use crate::{config, digest, git, lock, paths, skills, template};
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const MAX_NAME_LEN: usize = 64;
+const MAX_DESCRIPTION_LEN: usize = 1024;
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+pub struct LintArgs<'a> {
+    pub names: &'a [String],
+    pub root: Option<&'a str>,
+    pub json: bool,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Serialize)]
+struct Diagnostic {
+    skill: String,
+    rule: &'static str,
+    severity: Severity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    message: String,
+}
+
+pub fn run_lint(args: LintArgs<'_>) -> Result<()> {
+    let project_root = git::ensure_git_repo()?;
+    let cfg = config::load_or_default()?;
+    let install_root_rel = args.root.unwrap_or(&cfg.default_root);
+    let install_root = paths::resolve_project_path(&project_root, install_root_rel);
+
+    let names = if args.names.is_empty() {
+        discover_skill_dirs(&install_root)?
+    } else {
+        args.names.to_vec()
+    };
+
+    let lockfile = lock::Lockfile::load_or_empty(&project_root.join("skills.lock.json"))?;
+    let mut diagnostics = Vec::new();
+    for name in &names {
+        let dir = install_root.join(name);
+        if !dir.is_dir() {
+            bail!("skill '{name}' not found under {}", install_root.display());
+        }
+        let mut lint = SkillLint {
+            skill: name,
+            locked: lockfile.skills.iter().any(|s| &s.install_name == name),
+            diagnostics: &mut diagnostics,
+        };
+        lint.check(&dir)?;
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+    } else {
+        for d in &diagnostics {
+            let label = match d.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            match &d.file {
+                Some(file) => println!("{}: {label}[{}] {file}: {}", d.skill, d.rule, d.message),
+                None => println!("{}: {label}[{}] {}", d.skill, d.rule, d.message),
+            }
+        }
+        println!(
+            "Linted {} skill(s): {errors} error(s), {warnings} warning(s).",
+            names.len()
+        );
+    }
+    if errors > 0 {
+        bail!("lint failed with {errors} error(s)");
+    }
+    Ok(())
+}
+
+fn discover_skill_dirs(install_root: &Path) -> Result<Vec<String>> {
+    if !install_root.exists() {
+        return Ok(Vec::new());
+    }
+    let mut names = Vec::new();
+    for entry in
+        fs::read_dir(install_root).with_context(|| format!("reading {}", install_root.display()))?
+    {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.file_type()?.is_dir() && !name.starts_with('.') {
+            names.push(name);
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+struct SkillLint<'a> {
+    skill: &'a str,
+    /// Installed from a repo, so the directory may be an `--alias`.
+    locked: bool,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+impl SkillLint<'_> {
+    fn report(&mut self, rule: &'static str, severity: Severity, file: Option<&str>, msg: String) {
+        self.diagnostics.push(Diagnostic {
+            skill: self.skill.to_string(),
+            rule,
+            severity,
+            file: file.map(str::to_string),
+            message: msg,
+        });
+    }
+
+    fn error(&mut self, rule: &'static str, file: Option<&str>, msg: String) {
+        self.report(rule, Severity::Error, file, msg);
+    }
+
+    fn check(&mut self, dir: &Path) -> Result<()> {
+        self.check_files(dir)?;
+
+        let skill_md = dir.join("SKILL.md");
+        if !skill_md.exists() {
+            self.error("missing-skill-md", None, "SKILL.md not found".into());
+            return Ok(());
+        }
+        let text = fs::read_to_string(&skill_md)
+            .with_context(|| format!("reading {}", skill_md.display()))?;
+        let meta = match skills::parse_skill_frontmatter_str(&text) {
+            Ok(meta) => meta,
+            Err(err) => {
+                self.error(
+                    "front-matter",
+                    Some("SKILL.md"),
+                    format!("invalid front-matter ({err:#})"),
+                );
+                return Ok(());
+            }
+        };
+        self.check_name(&meta.name);
+        self.check_description(&meta.description);
+
+        let body = skills::strip_frontmatter(&text);
+        if body.trim().is_empty() {
+            self.error(
+                "empty-body",
+                Some("SKILL.md"),
+                "SKILL.md has no instructions after the front-matter".into(),
+            );
+        }
+        self.check_links(dir, body)?;
+        Ok(())
+    }
+
+    fn check_name(&mut self, name: &str) {
+        if let Err(err) = template::validate_skill_name(name) {
+            self.error("name", Some("SKILL.md"), err.to_string());
+            return;
+        }
+        let well_formed = name.len() <= MAX_NAME_LEN
+            && !name.starts_with('-')
+            && !name.ends_with('-')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !well_formed {
+            self.error(
+                "name",
+                Some("SKILL.md"),
+                format!(
+                    "name '{name}' must be at most {MAX_NAME_LEN} lowercase letters, digits, or hyphens"
+                ),
+            );
+        }
+        if name != self.skill {
+            let severity = if self.locked {
+                Severity::Warning
+            } else {
+                Severity::Error
+            };
+            self.report(
+                "name-mismatch",
+                severity,
+                Some("SKILL.md"),
+                format!("name '{name}' does not match directory '{}'", self.skill),
+            );
+        }
+    }
+
+    fn check_description(&mut self, description: &str) {
+        let len = description.trim().chars().count();
+        if len == 0 {
+            self.error(
+                "description",
+                Some("SKILL.md"),
+                "description is empty".into(),
+            );
+        } else if len > MAX_DESCRIPTION_LEN {
+            self.error(
+                "description",
+                Some("SKILL.md"),
+                format!("description is {len} characters (limit {MAX_DESCRIPTION_LEN})"),
+            );
+        }
+    }
+
+    fn check_links(&mut self, dir: &Path, body: &str) -> Result<()> {
+        let link = Regex::new(r#"!?\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#)?;
+        for caps in link.captures_iter(body) {
+            let target = &caps[1];
+            let Some(rel) = local_link_path(target) else {
+                continue;
+            };
+            if escapes_root(&rel) {
+                self.error(
+                    "broken-link",
+                    Some("SKILL.md"),
+                    format!("link '{target}' points outside the skill directory"),
+                );
+            } else if !dir.join(&rel).exists() {
+                self.error(
+                    "broken-link",
+                    Some("SKILL.md"),
+                    format!("link '{target}' does not resolve to a bundled file"),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn check_files(&mut self, dir: &Path) -> Result<()> {
+        for rel in digest::list_files(dir) {
+            let path = dir.join(&rel);
+            let display = rel.to_string_lossy().replace('\\', "/");
+            let size = fs::metadata(&path)?.len();
+            if size > MAX_FILE_BYTES {
+                self.error(
+                    "oversized-file",
+                    Some(&display),
+                    format!("{size} bytes exceeds the {MAX_FILE_BYTES} byte limit"),
+                );
+                continue;
+            }
+            let data = fs::read(&path).with_context(|| format!("reading {}", path.display()))?;
+            if digest::looks_binary(&data) {
+                self.report(
+                    "binary-file",
+                    Severity::Warning,
+                    Some(&display),
+                    "binary file bundled with the skill".into(),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Relative path for links that should resolve inside the skill, or `None` for
+/// URLs, anchors, and absolute paths.
+fn local_link_path(target: &str) -> Option<PathBuf> {
+    if target.starts_with('#') || target.starts_with('/') || target.contains(':') {
+        return None;
+    }
+    let path = target.split(['#', '?']).next().unwrap_or(target);
+    if path.is_empty() {
+        return None;
+    }
+    Some(PathBuf::from(path))
+}
+
+fn escapes_root(rel: &Path) -> bool {
+    let mut depth = 0i32;
+    for component in rel.components() {
+        match component {
+            Component::ParentDir => depth -= 1,
+            Component::Normal(_) => depth += 1,
+            _ => {}
+        }
+        if depth < 0 {
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,225 @@
+//! Skills imported from a plain directory or a `.zip` export instead of a git
+//! repo. The lockfile records where they came from plus the usual content
+//! digest; there is no commit to track.
+
+use crate::{skills, sync};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// True when `input` names a skill folder (SKILL.md at its top, not a git
+/// checkout) or a `.zip` file, rather than something `git` should fetch.
+pub fn is_local_input(input: &str) -> bool {
+    let path = Path::new(input);
+    if is_zip(path) {
+        return path.is_file();
+    }
+    path.is_dir() && path.join("SKILL.md").is_file() && !path.join(".git").exists()
+}
+
+pub fn is_zip(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+}
+
+/// Lockfile form of an origin: relative to the project root when it lives
+/// inside the repo (so collaborators can rebuild from it), absolute otherwise.
+pub fn stored_origin(project_root: &Path, origin: &Path) -> Result<String> {
+    let abs =
+        fs::canonicalize(origin).with_context(|| format!("resolving {}", origin.display()))?;
+    let root = fs::canonicalize(project_root)?;
+    Ok(match abs.strip_prefix(&root) {
+        Ok(rel) => rel.to_string_lossy().replace('\\', "/"),
+        Err(_) => abs.to_string_lossy().to_string(),
+    })
+}
+
+pub fn resolve_origin(project_root: &Path, stored: &str) -> PathBuf {
+    let path = Path::new(stored);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        project_root.join(path)
+    }
+}
+
+/// Whether collaborators can rebuild from this origin (it is checked into the repo).
+pub fn is_shared_origin(stored: &str) -> bool {
+    !Path::new(stored).is_absolute()
+}
+
+/// A directory origin as-is, or a zip unpacked into a temporary directory.
+pub struct Unpacked {
+    _tmp: Option<TempDir>,
+    root: PathBuf,
+}
+
+impl Unpacked {
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+pub fn unpack(origin: &Path) -> Result<Unpacked> {
+    if !origin.exists() {
+        bail!("local source {} no longer exists", origin.display());
+    }
+    if !is_zip(origin) {
+        return Ok(Unpacked {
+            _tmp: None,
+            root: origin.to_path_buf(),
+        });
+    }
+    let tmp = tempfile::tempdir().context("create temporary directory for zip")?;
+    extract_zip(origin, tmp.path())?;
+    Ok(Unpacked {
+        root: tmp.path().to_path_buf(),
+        _tmp: Some(tmp),
+    })
+}
+
+fn extract_zip(archive_path: &Path, dest: &Path) -> Result<()> {
+    let file = fs::File::open(archive_path)
+        .with_context(|| format!("opening {}", archive_path.display()))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .with_context(|| format!("reading zip {}", archive_path.display()))?;
+    for idx in 0..archive.len() {
+        let mut entry = archive.by_index(idx)?;
+        let Some(rel) = entry.enclosed_name() else {
+            bail!("zip entry '{}' escapes the archive root", entry.name());
+        };
+        if rel.components().any(|c| c.as_os_str() == "__MACOSX") {
+            continue;
+        }
+        // An earlier symlink entry must not redirect this write elsewhere.
+        refuse_symlinked_path(dest, &rel)?;
+        let target = dest.join(&rel);
+        if entry.is_dir() {
+            fs::create_dir_all(&target)?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        #[cfg(unix)]
+        if entry.is_symlink() {
+            let mut link = String::new();
+            io::Read::read_to_string(&mut entry, &mut link)?;
+            if !link_is_contained(dest, &rel, Path::new(&link)) {
+                bail!(
+                    "zip entry '{}' links to '{link}'; symlinks in a skill archive must point down into their own directory, without '..' or other symlinks",
+                    entry.name()
+                );
+            }
+            std::os::unix::fs::symlink(&link, &target)
+                .with_context(|| format!("linking {}", target.display()))?;
+            continue;
+        }
+        let mut out =
+            fs::File::create(&target).with_context(|| format!("writing {}", target.display()))?;
+        io::copy(&mut entry, &mut out)?;
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&target, fs::Permissions::from_mode(mode & 0o777))?;
+        }
+    }
+    Ok(())
+}
+
+/// Fail if `rel` (or any directory on the way to it) is already a symlink
+/// under `dest`.
+fn refuse_symlinked_path(dest: &Path, rel: &Path) -> Result<()> {
+    if let Some(link) = first_symlink(dest, rel) {
+        bail!(
+            "zip entry '{}' would be written through the symlink {}",
+            rel.display(),
+            link.display()
+        );
+    }
+    Ok(())
+}
+
+fn first_symlink(dest: &Path, rel: &Path) -> Option<PathBuf> {
+    let mut current = dest.to_path_buf();
+    for component in rel.components() {
+        current.push(component);
+        match fs::symlink_metadata(&current) {
+            Ok(meta) if meta.file_type().is_symlink() => return Some(current),
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    None
+}
+
+/// Whether a symlink at `entry` pointing to `link` stays below the link's
+/// own directory. Absolute targets and `..` are refused outright, as is a
+/// target that passes through a symlink extracted earlier: judging them
+/// against the archive root would let chained links, or a link from the
+/// skill into a sibling folder of a wrapper directory, slip through.
+#[cfg(unix)]
+fn link_is_contained(dest: &Path, entry: &Path, link: &Path) -> bool {
+    use std::path::Component;
+    if link.as_os_str().is_empty()
+        || link
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return false;
+    }
+    let parent = entry.parent().unwrap_or(Path::new(""));
+    first_symlink(dest, &parent.join(link)).is_none()
+}
+
+/// Find the single skill inside an unpacked origin. Zip exports usually wrap
+/// the skill in one top-level folder, so SKILL.md may sit one level down.
+pub fn discover(root: &Path) -> Result<skills::DiscoveredSkill> {
+    let mut found: Vec<PathBuf> = walkdir::WalkDir::new(root)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name() == "SKILL.md")
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    found.sort();
+    let skill_md = match found.as_slice() {
+        [] => bail!(
+            "no SKILL.md found in {}. A Claude Skill must contain SKILL.md with 'name' and 'description'.",
+            root.display()
+        ),
+        [only] => only.clone(),
+        _ if root.join("SKILL.md").is_file() => root.join("SKILL.md"),
+        _ => bail!(
+            "{} contains more than one SKILL.md; point sk install at a single skill",
+            root.display()
+        ),
+    };
+    let meta = skills::parse_frontmatter_file(&skill_md)?;
+    let dir = skill_md.parent().unwrap_or(root);
+    let rel = dir.strip_prefix(root).unwrap_or(Path::new(""));
+    let skill_path = if rel.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        rel.to_string_lossy().replace('\\', "/")
+    };
+    Ok(skills::DiscoveredSkill { skill_path, meta })
+}
+
+/// Copy the skill at `skill_path` inside `origin` to `dest`, honoring `.skignore`.
+pub fn import(origin: &Path, skill_path: &str, dest: &Path) -> Result<()> {
+    let unpacked = unpack(origin)?;
+    let src = if skill_path == "." {
+        unpacked.root().to_path_buf()
+    } else {
+        unpacked.root().join(skill_path)
+    };
+    if !src.join("SKILL.md").is_file() {
+        bail!("no SKILL.md at '{skill_path}' in {}", origin.display());
+    }
+    fs::create_dir_all(dest)?;
+    sync::fs_utils::mirror_dir(&src, dest)
+}
@@ -5,9 +5,16 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+/// First lockfile version whose skills may carry tracking fields (`"ref"`,
+/// `"constraint"`). Older lockfiles used `"ref"` for a field that has since
+/// been dropped, so it must not be mistaken for tracking there.
+pub const TRACKED_REF_LOCK_VERSION: u32 = 2;
+
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct Lockfile {
     pub version: u32,
+    #[serde(rename = "digestVersion", default = "legacy_digest_version")]
+    pub digest_version: u32,
     #[serde(default)]
     pub repos: RepoRegistry,
     pub skills: Vec<LockSkill>,
@@ -20,11 +27,16 @@ pub struct LockSkill {
     #[serde(rename = "installName")]
     pub install_name: String,
     pub source: Source,
-    #[allow(dead_code)]
-    #[serde(default, rename = "ref", skip_serializing)]
-    pub legacy_ref: Option<String>,
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<String>,
+    /// Empty for skills imported from a local directory or zip.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub commit: String,
     pub digest: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<crate::digest::FileEntry>,
     #[serde(rename = "installedAt")]
     pub installed_at: String,
 }
@@ -53,6 +65,8 @@ pub struct Source {
     repo_key: String,
     skill_path: String,
     spec: Option<crate::git::RepoSpec>,
+    /// Directory or zip path for skills installed without git.
+    local_path: Option<String>,
 }
 
 impl Source {
@@ -61,9 +75,32 @@ impl Source {
             repo_key: repo_key(&spec),
             skill_path,
             spec: Some(spec),
+            local_path: None,
         }
     }
 
+    pub fn new_local(path: String, skill_path: String) -> Self {
+        Self {
+            repo_key: format!("local:{path}"),
+            skill_path,
+            spec: None,
+            local_path: Some(path),
+        }
+    }
+
+    pub fn local_path(&self) -> Option<&str> {
+        self.local_path.as_deref()
+    }
+
+    pub fn is_local(&self) -> bool {
+        self.local_path.is_some()
+    }
+
+    /// The git repo behind this source, or `None` for local imports.
+    pub fn git_spec(&self) -> Option<&crate::git::RepoSpec> {
+        self.spec.as_ref()
+    }
+
     pub fn repo_key(&self) -> &str {
         &self.repo_key
     }
@@ -87,6 +124,26 @@ impl Source {
     }
 }
 
+impl LockSkill {
+    /// Per-file changes in `dir` relative to the locked manifest. Returns `None`
+    /// for entries locked before manifests were recorded.
+    pub fn manifest_changes(&self, dir: &std::path::Path) -> Option<crate::digest::ManifestDiff> {
+        if self.files.is_empty() {
+            return None;
+        }
+        let current = crate::digest::manifest_dir(dir).ok()?;
+        Some(crate::digest::diff_manifest(&self.files, &current))
+    }
+
+    pub fn tracking(&self) -> crate::git::Tracking<'_> {
+        crate::git::Tracking {
+            git_ref: self.git_ref.as_deref(),
+            constraint: self.constraint.as_deref(),
+            skill_path: self.source.skill_path(),
+        }
+    }
+}
+
 impl RepoRegistry {
     pub fn touch(&mut self) {
         self.updated_at = Some(Utc::now().to_rfc3339());
@@ -190,6 +247,21 @@ impl Serialize for Source {
             #[serde(rename = "skillPath")]
             skill_path: &'a str,
         }
+        #[derive(Serialize)]
+        struct LocalOut<'a> {
+            kind: &'static str,
+            path: &'a str,
+            #[serde(rename = "skillPath")]
+            skill_path: &'a str,
+        }
+        if let Some(path) = &self.local_path {
+            return LocalOut {
+                kind: "local",
+                path,
+                skill_path: &self.skill_path,
+            }
+            .serialize(serializer);
+        }
         let helper = SourceOut {
             repo_key: &self.repo_key,
             skill_path: &self.skill_path,
@@ -214,6 +286,12 @@ impl<'de> Deserialize<'de> for Source {
                 #[serde(rename = "skillPath")]
                 skill_path: String,
             },
+            Local {
+                kind: LocalKind,
+                path: String,
+                #[serde(rename = "skillPath")]
+                skill_path: String,
+            },
             Current {
                 #[serde(rename = "repoKey")]
                 repo_key: String,
@@ -222,6 +300,12 @@ impl<'de> Deserialize<'de> for Source {
             },
         }
 
+        #[derive(Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        enum LocalKind {
+            Local,
+        }
+
         match SourceSerde::deserialize(deserializer)? {
             SourceSerde::Legacy {
                 url,
@@ -236,12 +320,13 @@ impl<'de> Deserialize<'de> for Source {
                     owner,
                     repo,
                 };
-                Ok(Source {
-                    repo_key: repo_key(&spec),
-                    skill_path,
-                    spec: Some(spec),
-                })
+                Ok(Source::new(spec, skill_path))
             }
+            SourceSerde::Local {
+                kind: LocalKind::Local,
+                path,
+                skill_path,
+            } => Ok(Source::new_local(path, skill_path)),
             SourceSerde::Current {
                 repo_key,
                 skill_path,
@@ -249,6 +334,7 @@ impl<'de> Deserialize<'de> for Source {
                 repo_key,
                 skill_path,
                 spec: None,
+                local_path: None,
             }),
         }
     }
@@ -258,6 +344,7 @@ impl Lockfile {
     pub fn empty_now() -> Self {
         Self {
             version: 1,
+            digest_version: crate::digest::LEGACY_DIGEST_VERSION,
             repos: RepoRegistry::default(),
             skills: vec![],
             generated_at: Utc::now().to_rfc3339(),
@@ -265,19 +352,57 @@ impl Lockfile {
     }
 
     pub fn assert_no_legacy_refs(&self) -> Result<()> {
-        if let Some(entry) = self.skills.iter().find(|s| s.legacy_ref.is_some()) {
+        if self.version >= TRACKED_REF_LOCK_VERSION {
+            return Ok(());
+        }
+        if let Some(entry) = self.skills.iter().find(|s| s.git_ref.is_some()) {
             bail!(
                 "skills.lock.json still contains \"ref\" for install '{}'. The field is obsolete—remove the \"ref\" key (or reinstall the skill) and re-run sk.",
                 entry.install_name
             );
         }
+        if let Some(entry) = self.skills.iter().find(|s| s.constraint.is_some()) {
+            bail!(
+                "skills.lock.json is version {} but records a \"constraint\" for install '{}', which needs version {TRACKED_REF_LOCK_VERSION}. Reinstall the skill with --constraint to rewrite it.",
+                self.version,
+                entry.install_name
+            );
+        }
         Ok(())
     }
 
+    /// Bump the version once any skill tracks a ref or constraint, so a
+    /// version-1 lockfile never carries either.
+    fn stamp_version(&mut self) {
+        if self
+            .skills
+            .iter()
+            .any(|s| s.git_ref.is_some() || s.constraint.is_some())
+        {
+            self.version = self.version.max(TRACKED_REF_LOCK_VERSION);
+        }
+    }
+
     pub fn load(path: &Path) -> Result<Self> {
         let data = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
         let mut lf: Lockfile =
             serde_json::from_slice(&data).with_context(|| format!("parsing {}", path.display()))?;
+        if !(crate::digest::LEGACY_DIGEST_VERSION..=crate::digest::LATEST_DIGEST_VERSION)
+            .contains(&lf.digest_version)
+        {
+            bail!(
+                "{} uses digestVersion {}, which this version of sk does not understand",
+                path.display(),
+                lf.digest_version
+            );
+        }
+        if lf.version > TRACKED_REF_LOCK_VERSION {
+            bail!(
+                "{} is lockfile version {}, which this version of sk does not understand",
+                path.display(),
+                lf.version
+            );
+        }
         lf.assert_no_legacy_refs()?;
         lf.repos.backfill_from_skills(&lf.skills);
         lf.hydrate_sources()?;
@@ -299,7 +424,7 @@ impl Lockfile {
         }
         let mut missing: Vec<String> = Vec::new();
         for skill in &mut self.skills {
-            if skill.source.spec.is_some() {
+            if skill.source.spec.is_some() || skill.source.is_local() {
                 continue;
             }
             if let Some(spec) = catalog.get(skill.source.repo_key()) {
@@ -347,6 +472,7 @@ where
         None
     };
     let result = mutate(&mut lf)?;
+    lf.stamp_version();
     let after = serde_json::to_vec(&lf)?;
     let changed = match before {
         Some(bytes) => bytes != after,
@@ -358,6 +484,10 @@ where
     Ok(result)
 }
 
+fn legacy_digest_version() -> u32 {
+    crate::digest::LEGACY_DIGEST_VERSION
+}
+
 pub fn repo_key(spec: &crate::git::RepoSpec) -> String {
     format!("{}/{}/{}", spec.host, spec.owner, spec.repo)
 }
@@ -1,20 +1,27 @@
 mod cli;
 mod config;
+mod deps;
 mod digest;
 mod doctor;
 mod git;
 mod install;
+mod lint;
+mod local;
 mod lock;
 mod mcp;
+mod pack;
 mod paths;
 mod precommit;
 mod remove;
 mod repo;
+mod restore;
+mod search;
 mod skills;
 mod sync;
 mod template;
 mod update;
 mod upgrade;
+mod versions;
 
 use anyhow::{bail, Context, Result};
 use clap::{CommandFactory, Parser};
@@ -37,11 +44,22 @@ fn main() -> Result<()> {
             CacheCmd::Refresh => update::run_cache_refresh(),
         },
         Commands::Upgrade {
-            target, dry_run, ..
+            target,
+            dry_run,
+            merge,
+            continue_merge,
+            abort_merge,
         } => upgrade::run_upgrade(upgrade::UpgradeArgs {
             target: &target,
             root: None,
             dry_run,
+            merge,
+            continue_merge,
+            abort_merge,
+        }),
+        Commands::Restore { root, force } => restore::run_restore(restore::RestoreArgs {
+            root: root.as_deref(),
+            force,
         }),
         Commands::Remove {
             installed_name,
@@ -53,20 +71,39 @@ fn main() -> Result<()> {
             force,
         }),
         Commands::SyncBack {
-            installed_name,
+            installed_names,
+            all_modified,
             branch,
             message,
             repo,
             skill_path,
             https,
+            dry_run,
+            rebase,
+            patch,
+            patch_format,
         } => sync::run_sync_back(sync::SyncBackArgs {
-            installed_name: &installed_name,
+            installed_names: installed_names.iter().map(String::as_str).collect(),
+            all_modified,
             branch: branch.as_deref(),
             message: message.as_deref(),
             root: None,
             repo: repo.as_deref(),
             skill_path: skill_path.as_deref(),
             https,
+            dry_run,
+            rebase,
+            patch: patch.as_deref(),
+            patch_format: patch_format.as_deref(),
+        }),
+        Commands::ApplyPatch {
+            installed_name,
+            patch_file,
+            root,
+        } => sync::run_apply_patch(sync::ApplyPatchArgs {
+            installed_name: &installed_name,
+            patch_file: &patch_file,
+            root: root.as_deref(),
         }),
         Commands::Doctor {
             names,
@@ -75,6 +112,7 @@ fn main() -> Result<()> {
             diff,
             json,
             apply,
+            migrate_digests,
         } => cmd_doctor(CmdDoctorConfig {
             names: &names,
             summary,
@@ -82,26 +120,56 @@ fn main() -> Result<()> {
             diff,
             json,
             apply,
+            migrate_digests,
         }),
         Commands::Config { cmd } => cmd_config(cmd),
         Commands::Template { cmd } => cmd_template(cmd),
         Commands::Repo { cmd } => cmd_repo(cmd),
+        Commands::Lint { names, root, json } => lint::run_lint(lint::LintArgs {
+            names: &names,
+            root: root.as_deref(),
+            json,
+        }),
+        Commands::Pack {
+            installed_name,
+            out,
+            root,
+        } => pack::run_pack(pack::PackArgs {
+            installed_name: &installed_name,
+            out: out.as_deref(),
+            root: root.as_deref(),
+        }),
+        Commands::Unpack {
+            archive,
+            alias,
+            root,
+        } => pack::run_unpack(pack::UnpackArgs {
+            archive: &archive,
+            alias: alias.as_deref(),
+            root: root.as_deref(),
+        }),
         Commands::Precommit { allow_local } => precommit::run_precommit(allow_local),
         Commands::McpServer { root } => mcp::run_server(root.as_deref()),
         Commands::Install {
             repo,
             skill_name,
+            all,
             alias,
             path,
             root,
             https,
+            git_ref,
+            constraint,
         } => install::run_install(install::InstallArgs {
             repo: &repo,
-            skill_name: &skill_name,
+            skill_name: skill_name.as_deref(),
+            all,
             alias: alias.as_deref(),
             path: path.as_deref(),
             root: root.as_deref(),
             https,
+            git_ref: git_ref.as_deref(),
+            constraint: constraint.as_deref(),
         }),
         Commands::Completions { shell } => {
             let mut cmd = Cli::command();
@@ -173,15 +241,19 @@ struct CmdDoctorConfig<'a> {
     diff: bool,
     json: bool,
     apply: bool,
+    migrate_digests: bool,
 }
 
 fn cmd_doctor(cfg: CmdDoctorConfig<'_>) -> Result<()> {
-    let mode_flags = cfg.summary as u8 + cfg.status as u8 + cfg.diff as u8;
+    let mode_flags =
+        cfg.summary as u8 + cfg.status as u8 + cfg.diff as u8 + cfg.migrate_digests as u8;
     if mode_flags > 1 {
-        bail!("Choose only one of --summary, --status, or --diff.");
+        bail!("Choose only one of --summary, --status, --diff, or --migrate-digests.");
     }
 
-    let mode = if cfg.summary {
+    let mode = if cfg.migrate_digests {
+        DoctorMode::MigrateDigests
+    } else if cfg.summary {
         DoctorMode::Summary
     } else if cfg.status {
         DoctorMode::Status
@@ -195,7 +267,7 @@ fn cmd_doctor(cfg: CmdDoctorConfig<'_>) -> Result<()> {
         bail!("--json is only supported with --summary or --status.");
     }
     if cfg.apply && !matches!(mode, DoctorMode::Diagnose) {
-        bail!("--apply cannot be combined with --summary/--status/--diff.");
+        bail!("--apply cannot be combined with --summary/--status/--diff/--migrate-digests.");
     }
 
     doctor::run_doctor(DoctorArgs {
@@ -209,7 +281,7 @@ fn cmd_doctor(cfg: CmdDoctorConfig<'_>) -> Result<()> {
 
 fn cmd_init(root_flag: Option<&str>) -> Result<()> {
     let project_root = git::ensure_git_repo()?;
-    let mut cfg = config::load_or_default()?;
+    let cfg = config::load_or_default()?;
     let install_root_rel = root_flag.unwrap_or(&cfg.default_root);
     let install_root = paths::resolve_project_path(&project_root, install_root_rel);
     std::fs::create_dir_all(&install_root)
@@ -229,44 +301,69 @@ fn cmd_init(root_flag: Option<&str>) -> Result<()> {
         );
     }
 
-    // Ensure user config is saved
-    if root_flag.is_some() && cfg.default_root != install_root_rel {
-        cfg.default_root = install_root_rel.to_string();
+    // Ensure user config is saved (project .sk.toml values stay out of it)
+    let mut user_cfg = config::load_user()?;
+    if root_flag.is_some() && user_cfg.default_root != install_root_rel {
+        user_cfg.default_root = install_root_rel.to_string();
     }
-    config::save_if_missing(&cfg)?;
+    config::save_if_missing(&user_cfg)?;
 
     println!("Initialized. Install root: {}", install_root.display());
     Ok(())
 }
 
+#[derive(Serialize)]
+struct ConfigRow {
+    key: &'static str,
+    value: String,
+    origin: String,
+}
+
 fn cmd_config(cmd: ConfigCmd) -> Result<()> {
     match cmd {
-        ConfigCmd::Get { key } => {
-            let cfg = config::load_or_default()?;
-            match key.as_str() {
-                "default_root" => println!("{}", cfg.default_root),
-                "protocol" => println!("{}", cfg.protocol),
-                "default_host" => println!("{}", cfg.default_host),
-                "github_user" => println!("{}", cfg.github_user),
-                "default_repo" => println!("{}", cfg.default_repo),
-                "template_source" => println!("{}", cfg.template_source),
-                _ => anyhow::bail!("Unknown key: {key}"),
+        ConfigCmd::Get { key, show_origin } => {
+            let layered = config::load_layered()?;
+            let value = layered.config.get(&key)?;
+            if show_origin {
+                println!("{}\t{value}", layered.origin(&key));
+            } else {
+                println!("{value}");
             }
         }
-        ConfigCmd::Set { key, value } => {
-            let mut cfg = config::load_or_default()?;
-            match key.as_str() {
-                "default_root" => cfg.default_root = value,
-                "protocol" => cfg.protocol = value,
-                "default_host" => cfg.default_host = value,
-                "github_user" => cfg.github_user = value,
-                "default_repo" => cfg.default_repo = value,
-                "template_source" => cfg.template_source = value,
-                _ => anyhow::bail!("Unknown key: {key}"),
+        ConfigCmd::Set {
+            key,
+            value,
+            project,
+        } => {
+            if project {
+                let project_root = git::ensure_git_repo()?;
+                let path = project_root.join(config::PROJECT_CONFIG_FILE);
+                config::set_project_value(&path, &key, &value)?;
+            } else {
+                config::set_user_value(&key, &value)?;
             }
-            config::save(&cfg)?;
             println!("ok");
         }
+        ConfigCmd::List { json } => {
+            let layered = config::load_layered()?;
+            let rows: Vec<ConfigRow> = config::KEYS
+                .iter()
+                .map(|key| ConfigRow {
+                    key,
+                    value: layered.config.get(key).unwrap_or_default().to_string(),
+                    origin: layered.origin(key).to_string(),
+                })
+                .collect();
+            if json {
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+            } else {
+                let value_width = rows.iter().map(|r| r.value.len()).max().unwrap_or(0).max(5);
+                println!("{:<16} {:<value_width$} ORIGIN", "KEY", "VALUE");
+                for row in &rows {
+                    println!("{:<16} {:<value_width$} {}", row.key, row.value, row.origin);
+                }
+            }
+        }
     }
     Ok(())
 }
@@ -286,16 +383,25 @@ fn cmd_list(_root_flag: Option<&str>, json: bool) -> Result<()> {
         .skills
         .iter()
         .map(|skill| {
-            let (display_name, description) = match load_skill_meta(&install_root, skill) {
-                Some(meta) => (meta.name, meta.description),
-                None => (skill.install_name.clone(), String::new()),
-            };
+            let (display_name, description, front_matter) =
+                match load_skill_meta(&install_root, skill) {
+                    Some(meta) => {
+                        let front_matter = meta.front_matter();
+                        (meta.name, meta.description, front_matter)
+                    }
+                    None => (
+                        skill.install_name.clone(),
+                        String::new(),
+                        skills::FrontMatterFields::default(),
+                    ),
+                };
             ListRow {
                 install_name: skill.install_name.clone(),
                 display_name,
                 repo: format_repo_id(skill),
                 skill_path: skill.source.skill_path().to_string(),
                 description,
+                front_matter,
             }
         })
         .collect();
@@ -338,10 +444,14 @@ struct ListRow {
     #[serde(rename = "skillPath")]
     skill_path: String,
     description: String,
+    #[serde(flatten)]
+    front_matter: skills::FrontMatterFields,
 }
 
 fn format_repo_id(skill: &lock::LockSkill) -> String {
-    let spec = skill.source.repo_spec();
+    let Some(spec) = skill.source.git_spec() else {
+        return skill.source.local_path().unwrap_or_default().to_string();
+    };
     let base = if spec.host == "local" {
         spec.url.clone()
     } else {
@@ -372,6 +482,18 @@ fn cmd_where(name: &str, root_flag: Option<&str>) -> Result<()> {
         println!("{}", path.display());
         Ok(())
     } else {
-        anyhow::bail!("not found: {name}")
+        let installed: Vec<String> = std::fs::read_dir(&install_root)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().join("SKILL.md").is_file())
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        anyhow::bail!(
+            "not found: {name}.{}",
+            search::did_you_mean(name, installed.iter().map(String::as_str))
+        )
     }
 }
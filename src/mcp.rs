@@ -1,16 +1,23 @@
 mod catalog;
+mod files;
 mod transport;
 
-use crate::{config, git, paths};
+use crate::{config, git, paths, search};
 use anyhow::{Context, Result};
-use catalog::{relative_path, scan_skills};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use catalog::{relative_path, SkillIndex, SkillRecord};
+use files::{list_skill_files, mime_for, read_skill_file, resolve_skill_file, FileContents};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use rmcp::{
     handler::server::{router::tool::ToolRouter, wrapper::Parameters, ServerHandler},
     model::{
-        CallToolResult, Content, Implementation, ListResourcesResult, Meta, PaginatedRequestParam,
-        ProtocolVersion, RawResource, ReadResourceRequestParam, ReadResourceResult, Resource,
-        ResourceContents, ServerCapabilities, ServerInfo,
+        CallToolResult, Content, GetPromptRequestParam, GetPromptResult, Implementation,
+        ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, Meta,
+        PaginatedRequestParam, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+        ProtocolVersion, RawResource, RawResourceTemplate, ReadResourceRequestParam,
+        ReadResourceResult, Resource, ResourceContents, ResourceTemplate, ServerCapabilities,
+        ServerInfo,
     },
     service::{Peer, RoleServer, ServiceExt},
     tool, tool_handler, tool_router,
@@ -21,6 +28,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::{
+    collections::BTreeMap,
     path::PathBuf,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -38,6 +46,7 @@ const MAX_SEARCH_LIMIT: usize = 25;
 const DEFAULT_SEARCH_LIMIT: usize = 10;
 const QUICKSTART_URI: &str = "sk://quickstart";
 const SKILL_URI_PREFIX: &str = "sk://skill/";
+const SKILL_FILE_URI_TEMPLATE: &str = "sk://skill/{name}/{path}";
 const QUICKSTART_DOC: &str = include_str!("../docs/AGENT_QUICKSTART.md");
 const BASE_SERVER_INSTRUCTIONS: &str = "Start every task with skills_search to confirm whether a repo skill applies, then use skills_list or skills_show to pull the relevant body text when needed.";
 
@@ -80,8 +89,12 @@ async fn serve_stdio(server: SkMcpServer) -> Result<()> {
     let initialized_flag = server.initialized.clone();
     let (watcher, notify_task) = if server.is_ready() {
         let (notify_tx, notify_rx) = mpsc::unbounded_channel();
-        let watcher = spawn_tool_watcher(server.skills_root.clone(), notify_tx)
-            .context("failed to start skills watcher")?;
+        let watcher =
+            spawn_tool_watcher(server.skills_root.clone(), server.index.clone(), notify_tx)
+                .context("failed to start skills watcher")?;
+        // Anything written between the startup scan and the watch going live
+        // would otherwise be missed until that skill changes again.
+        server.index.rebuild();
         let peer_clone = peer.clone();
         let init_clone = initialized_flag.clone();
         let notify_task =
@@ -107,6 +120,7 @@ async fn serve_stdio(server: SkMcpServer) -> Result<()> {
 #[derive(Debug, Clone, Copy)]
 enum NotificationEvent {
     ToolsListChanged,
+    PromptsListChanged,
 }
 
 async fn forward_notifications(
@@ -125,12 +139,19 @@ async fn forward_notifications(
                     break;
                 }
             }
+            NotificationEvent::PromptsListChanged => {
+                if let Err(err) = peer.notify_prompt_list_changed().await {
+                    eprintln!("failed to emit prompts/list_changed notification: {err}");
+                    break;
+                }
+            }
         }
     }
 }
 
 fn spawn_tool_watcher(
     skills_root: PathBuf,
+    index: Arc<SkillIndex>,
     tx: UnboundedSender<NotificationEvent>,
 ) -> Result<RecommendedWatcher> {
     let (watch_tx, watch_rx) = std_mpsc::channel();
@@ -146,11 +167,18 @@ fn spawn_tool_watcher(
         while let Ok(event) = watch_rx.recv() {
             match event {
                 Ok(evt) => {
+                    if evt.need_rescan() {
+                        index.rebuild();
+                    }
                     if transport::relevant_event(&evt.kind) {
+                        // Keep the index current on every event; only the
+                        // client notifications are debounced.
+                        index.refresh_paths(&evt.paths);
                         let now = Instant::now();
                         if now.duration_since(last_emit) >= debounce {
                             last_emit = now;
                             let _ = tx.send(NotificationEvent::ToolsListChanged);
+                            let _ = tx.send(NotificationEvent::PromptsListChanged);
                         }
                     }
                 }
@@ -168,6 +196,7 @@ fn spawn_tool_watcher(
 struct SkMcpServer {
     project_root: PathBuf,
     skills_root: PathBuf,
+    index: Arc<SkillIndex>,
     tool_router: ToolRouter<Self>,
     initialized: Arc<AtomicBool>,
     availability: Availability,
@@ -181,9 +210,11 @@ enum Availability {
 
 impl SkMcpServer {
     fn new(project_root: PathBuf, skills_root: PathBuf, availability: Availability) -> Self {
+        let index = Arc::new(SkillIndex::build(&project_root, &skills_root));
         Self {
             project_root,
             skills_root,
+            index,
             tool_router: Self::tool_router(),
             initialized: Arc::new(AtomicBool::new(false)),
             availability,
@@ -224,8 +255,7 @@ impl SkMcpServer {
 
     fn list_skills(&self, args: ListArgs) -> Result<CallToolResult, McpError> {
         self.guard_ready()?;
-        let skills =
-            scan_skills(&self.project_root, &self.skills_root).map_err(to_internal_error)?;
+        let skills = self.index.records();
         let filtered: Vec<_> = if let Some(query) = args.query.as_deref().map(str::trim) {
             if query.is_empty() {
                 skills
@@ -258,11 +288,7 @@ impl SkMcpServer {
         if query.is_empty() {
             return Err(McpError::invalid_params("query must not be empty", None));
         }
-        let tokens: Vec<String> = query
-            .split_whitespace()
-            .map(|s| s.to_ascii_lowercase())
-            .collect();
-        if tokens.is_empty() {
+        if search::tokenize(query).is_empty() {
             return Err(McpError::invalid_params(
                 "query must include at least one token",
                 None,
@@ -272,28 +298,18 @@ impl SkMcpServer {
             .limit
             .unwrap_or(DEFAULT_SEARCH_LIMIT)
             .clamp(1, MAX_SEARCH_LIMIT);
-        let skills =
-            scan_skills(&self.project_root, &self.skills_root).map_err(to_internal_error)?;
-        let mut hits: Vec<_> = skills
-            .iter()
-            .filter_map(|skill| skill.score_for_tokens(&tokens))
-            .collect();
-        hits.sort_by(|a, b| {
-            b.score
-                .cmp(&a.score)
-                .then_with(|| a.install_name.cmp(&b.install_name))
-        });
+        let mut hits = self.index.search(query);
         let total = hits.len();
         hits.truncate(limit);
         let results: Vec<_> = hits
             .into_iter()
             .map(|hit| SearchHitPayload {
-                name: hit.meta.name,
-                description: hit.meta.description,
-                install_name: hit.install_name,
-                skill_path: hit.skill_path,
-                skill_file: hit.skill_file,
-                score: hit.score,
+                name: hit.record.meta.name.clone(),
+                description: hit.record.meta.description.clone(),
+                install_name: hit.record.install_name.clone(),
+                skill_path: hit.record.skill_path.clone(),
+                skill_file: hit.record.skill_file.clone(),
+                score: (hit.score * 1000.0).round() / 1000.0,
                 excerpt: hit.excerpt,
             })
             .collect();
@@ -327,14 +343,21 @@ impl SkMcpServer {
                 None,
             ));
         }
-        let skills =
-            scan_skills(&self.project_root, &self.skills_root).map_err(to_internal_error)?;
+        let skills = self.index.records();
         let Some(record) = skills
-            .into_iter()
+            .iter()
             .find(|skill| skill.meta.name.eq_ignore_ascii_case(raw))
+            .or_else(|| {
+                skills
+                    .iter()
+                    .find(|skill| skill.install_name.eq_ignore_ascii_case(raw))
+            })
         else {
+            let names = skills
+                .iter()
+                .flat_map(|skill| [skill.meta.name.as_str(), skill.install_name.as_str()]);
             return Err(McpError::invalid_params(
-                format!("unknown skill: {raw}"),
+                format!("unknown skill: {raw}.{}", search::did_you_mean(raw, names)),
                 None,
             ));
         };
@@ -360,20 +383,50 @@ impl SkMcpServer {
     }
 
     fn skill_resources(&self) -> Result<Vec<Resource>, McpError> {
-        let skills =
-            scan_skills(&self.project_root, &self.skills_root).map_err(to_internal_error)?;
-        Ok(skills
+        let skills = self.index.records();
+        let mut resources = Vec::new();
+        for skill in skills {
+            let uri = skill_resource_uri(&skill.install_name);
+            let mut raw = RawResource::new(uri, skill.install_name.clone());
+            raw.title = Some(skill.meta.name.clone());
+            raw.description = Some(skill.meta.description.clone());
+            raw.mime_type = Some("text/markdown".into());
+            raw.size = Some(skill.body.len() as u32);
+            resources.push(Resource::new(raw, None));
+            resources.extend(self.skill_file_resources(&skill));
+        }
+        Ok(resources)
+    }
+
+    fn skill_file_resources(&self, skill: &SkillRecord) -> Vec<Resource> {
+        let skill_dir = self.project_root.join(&skill.skill_path);
+        list_skill_files(&skill_dir)
             .into_iter()
-            .map(|skill| {
-                let uri = skill_resource_uri(&skill.install_name);
-                let mut raw = RawResource::new(uri, skill.install_name.clone());
-                raw.title = Some(skill.meta.name.clone());
-                raw.description = Some(skill.meta.description.clone());
-                raw.mime_type = Some("text/markdown".into());
-                raw.size = Some(skill.body.len() as u32);
+            .map(|rel| {
+                let path = skill_dir.join(&rel);
+                let name = format!("{}/{rel}", skill.install_name);
+                let mut raw = RawResource::new(skill_file_uri(&skill.install_name, &rel), name);
+                raw.description = Some(format!("File bundled with the {} skill", skill.meta.name));
+                raw.mime_type = Some(mime_for(&path).into());
+                raw.size = path.metadata().ok().map(|m| m.len() as u32);
                 Resource::new(raw, None)
             })
-            .collect())
+            .collect()
+    }
+
+    fn skill_file_template(&self) -> ResourceTemplate {
+        ResourceTemplate::new(
+            RawResourceTemplate {
+                uri_template: SKILL_FILE_URI_TEMPLATE.into(),
+                name: "skill-file".into(),
+                title: Some("Skill file".into()),
+                description: Some(
+                    "A script, reference, or template bundled in an installed skill's directory; path is relative to the skill root.".into(),
+                ),
+                mime_type: None,
+            },
+            None,
+        )
     }
 
     fn read_skill_resource(&self, uri: &str) -> Result<ReadResourceResult, McpError> {
@@ -384,14 +437,20 @@ impl SkMcpServer {
                 None,
             ));
         };
+        let Some((name, file)) = decode_skill_uri_path(name) else {
+            return Err(McpError::resource_not_found(
+                format!("malformed skill resource URI: {uri}"),
+                None,
+            ));
+        };
+        let name = name.as_str();
         if name.trim().is_empty() {
             return Err(McpError::resource_not_found(
                 "skill resource name must not be empty".to_string(),
                 None,
             ));
         }
-        let skills =
-            scan_skills(&self.project_root, &self.skills_root).map_err(to_internal_error)?;
+        let skills = self.index.records();
         let Some(skill) = skills
             .into_iter()
             .find(|skill| skill.install_name.eq_ignore_ascii_case(name))
@@ -401,6 +460,9 @@ impl SkMcpServer {
                 None,
             ));
         };
+        if let Some(file) = file {
+            return self.read_skill_file_resource(uri, &skill, &file);
+        }
         let mut meta = Meta::new();
         meta.insert("installName".into(), json!(skill.install_name));
         meta.insert("skillPath".into(), json!(skill.skill_path));
@@ -409,11 +471,104 @@ impl SkMcpServer {
             contents: vec![ResourceContents::TextResourceContents {
                 uri: uri.to_string(),
                 mime_type: Some("text/markdown".into()),
-                text: skill.body,
+                text: skill.body.clone(),
                 meta: Some(meta),
             }],
         })
     }
+
+    fn read_skill_file_resource(
+        &self,
+        uri: &str,
+        skill: &SkillRecord,
+        file: &str,
+    ) -> Result<ReadResourceResult, McpError> {
+        let skill_dir = self.project_root.join(&skill.skill_path);
+        let path = resolve_skill_file(&skill_dir, file).map_err(|err| {
+            McpError::resource_not_found(format!("{}: {err}", skill.install_name), None)
+        })?;
+        let (contents, mime) = read_skill_file(&path).map_err(to_internal_error)?;
+        let mut meta = Meta::new();
+        meta.insert("installName".into(), json!(skill.install_name));
+        meta.insert("skillPath".into(), json!(skill.skill_path));
+        meta.insert("path".into(), json!(file));
+        let contents = match contents {
+            FileContents::Text(text) => ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(mime.into()),
+                text,
+                meta: Some(meta),
+            },
+            FileContents::Binary(bytes) => ResourceContents::BlobResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(mime.into()),
+                blob: BASE64.encode(bytes),
+                meta: Some(meta),
+            },
+        };
+        Ok(ReadResourceResult {
+            contents: vec![contents],
+        })
+    }
+
+    fn skill_prompts(&self) -> Result<Vec<Prompt>, McpError> {
+        let skills = self.index.records();
+        Ok(skills
+            .into_iter()
+            .map(|skill| {
+                let arguments: Vec<_> = skill
+                    .prompt_arguments()
+                    .into_iter()
+                    .map(|arg| PromptArgument {
+                        name: arg.name,
+                        title: None,
+                        description: arg.description,
+                        required: Some(arg.required),
+                    })
+                    .collect();
+                let mut prompt = Prompt::new(
+                    skill.install_name.clone(),
+                    Some(skill.meta.description.clone()),
+                    (!arguments.is_empty()).then_some(arguments),
+                );
+                prompt.title = Some(skill.meta.name.clone());
+                prompt
+            })
+            .collect())
+    }
+
+    fn render_skill_prompt(
+        &self,
+        request: GetPromptRequestParam,
+    ) -> Result<GetPromptResult, McpError> {
+        self.guard_ready()?;
+        let skills = self.index.records();
+        let Some(skill) = skills
+            .into_iter()
+            .find(|skill| skill.install_name.eq_ignore_ascii_case(&request.name))
+        else {
+            return Err(McpError::invalid_params(
+                format!("unknown prompt: {}", request.name),
+                None,
+            ));
+        };
+        let values: BTreeMap<String, String> = request
+            .arguments
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(key, value)| match value {
+                Value::String(text) => (key, text),
+                other => (key, other.to_string()),
+            })
+            .collect();
+        let text = skill
+            .render_prompt(&values)
+            .map_err(|err| McpError::invalid_params(err.to_string(), None))?;
+        Ok(GetPromptResult {
+            description: Some(skill.meta.description.clone()),
+            messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+        })
+    }
 }
 
 #[tool_router]
@@ -461,6 +616,8 @@ impl ServerHandler for SkMcpServer {
                 .enable_tools()
                 .enable_tool_list_changed()
                 .enable_resources()
+                .enable_prompts()
+                .enable_prompts_list_changed()
                 .build(),
             server_info: server_implementation(),
             instructions: Some(self.instructions_text()),
@@ -487,6 +644,17 @@ impl ServerHandler for SkMcpServer {
         std::future::ready(result)
     }
 
+    fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: rmcp::service::RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<ListResourceTemplatesResult, McpError>> + Send + '_
+    {
+        std::future::ready(Ok(ListResourceTemplatesResult::with_all_items(vec![
+            self.skill_file_template()
+        ])))
+    }
+
     fn read_resource(
         &self,
         request: ReadResourceRequestParam,
@@ -509,6 +677,27 @@ impl ServerHandler for SkMcpServer {
         })
     }
 
+    fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: rmcp::service::RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<ListPromptsResult, McpError>> + Send + '_ {
+        let result = if self.is_ready() {
+            self.skill_prompts()
+        } else {
+            Ok(Vec::new())
+        };
+        std::future::ready(result.map(ListPromptsResult::with_all_items))
+    }
+
+    fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: rmcp::service::RequestContext<RoleServer>,
+    ) -> impl std::future::Future<Output = Result<GetPromptResult, McpError>> + Send + '_ {
+        std::future::ready(self.render_skill_prompt(request))
+    }
+
     fn on_initialized(
         &self,
         _context: rmcp::service::NotificationContext<RoleServer>,
@@ -544,7 +733,7 @@ struct SearchHitPayload {
     install_name: String,
     skill_path: String,
     skill_file: String,
-    score: usize,
+    score: f64,
     excerpt: String,
 }
 
@@ -561,8 +750,47 @@ fn to_internal_error(err: anyhow::Error) -> McpError {
     McpError::internal_error(err.to_string(), None)
 }
 
+/// Everything but RFC 3986 unreserved characters gets percent-encoded in a
+/// URI path segment.
+const URI_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
 fn skill_resource_uri(install_name: &str) -> String {
-    format!("{SKILL_URI_PREFIX}{install_name}")
+    format!(
+        "{SKILL_URI_PREFIX}{}",
+        utf8_percent_encode(install_name, URI_SEGMENT)
+    )
+}
+
+/// `sk://skill/{name}/{path}`, encoding each segment of `rel` separately so
+/// its slashes stay path separators.
+fn skill_file_uri(install_name: &str, rel: &str) -> String {
+    let path: Vec<String> = rel
+        .split('/')
+        .map(|segment| utf8_percent_encode(segment, URI_SEGMENT).to_string())
+        .collect();
+    format!("{}/{}", skill_resource_uri(install_name), path.join("/"))
+}
+
+/// Split the part of a skill URI after `sk://skill/` into the install name
+/// and, for bundled files, the decoded relative path.
+fn decode_skill_uri_path(raw: &str) -> Option<(String, Option<String>)> {
+    let decode = |segment: &str| {
+        percent_decode_str(segment)
+            .decode_utf8()
+            .ok()
+            .map(|s| s.into_owned())
+    };
+    match raw.split_once('/') {
+        Some((name, file)) => {
+            let segments: Option<Vec<String>> = file.split('/').map(decode).collect();
+            Some((decode(name)?, Some(segments?.join("/"))))
+        }
+        None => Some((decode(raw)?, None)),
+    }
 }
 
 fn server_implementation() -> Implementation {
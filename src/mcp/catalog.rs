@@ -1,8 +1,11 @@
-use crate::skills;
-use anyhow::Result;
+use crate::{search, skills};
+use anyhow::{bail, Result};
+use regex::{Captures, Regex};
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use walkdir::WalkDir;
 
 #[derive(Clone)]
@@ -12,7 +15,6 @@ pub(crate) struct SkillRecord {
     pub(crate) skill_file: String,
     pub(crate) meta: skills::SkillMeta,
     pub(crate) body: String,
-    body_ascii_lower: String,
     search_blob: String,
 }
 
@@ -35,32 +37,92 @@ impl SkillRecord {
             skill_path: self.skill_path.clone(),
             skill_file: self.skill_file.clone(),
             body: self.body.clone(),
+            front_matter: self.meta.front_matter(),
         }
     }
 
-    pub(crate) fn score_for_tokens(&self, tokens: &[String]) -> Option<SearchMatch> {
-        let mut score = 0;
-        for token in tokens {
-            if self.search_blob.contains(token) {
-                score += 1;
-            } else {
-                return None;
-            }
+    pub(crate) fn matches_query(&self, needle: &str) -> bool {
+        self.search_blob.contains(needle)
+    }
+
+    /// Arguments declared under `arguments:` in the front-matter, either as
+    /// bare names or as `{name, description, required, default}` maps.
+    pub(crate) fn prompt_arguments(&self) -> Vec<PromptArg> {
+        let Some(serde_yaml::Value::Sequence(items)) = self.meta.extra.get("arguments") else {
+            return Vec::new();
+        };
+        items
+            .iter()
+            .filter_map(|item| match item {
+                serde_yaml::Value::String(name) => Some(PromptArg {
+                    name: name.trim().to_string(),
+                    description: None,
+                    required: false,
+                    default: None,
+                }),
+                serde_yaml::Value::Mapping(map) => {
+                    let field = |key: &str| map.get(key).and_then(yaml_scalar);
+                    Some(PromptArg {
+                        name: field("name")?.trim().to_string(),
+                        description: field("description"),
+                        required: map
+                            .get("required")
+                            .and_then(serde_yaml::Value::as_bool)
+                            .unwrap_or(false),
+                        default: field("default"),
+                    })
+                }
+                _ => None,
+            })
+            .filter(|arg| !arg.name.is_empty())
+            .collect()
+    }
+
+    /// Substitute `{{name}}` placeholders for declared arguments in the body.
+    /// Omitted optional arguments fall back to their default (or nothing);
+    /// placeholders that match no declared argument are left as written.
+    pub(crate) fn render_prompt(&self, values: &BTreeMap<String, String>) -> Result<String> {
+        let declared = self.prompt_arguments();
+        if let Some(missing) = declared
+            .iter()
+            .find(|arg| arg.required && !values.contains_key(&arg.name))
+        {
+            bail!(
+                "missing required argument '{}' for prompt '{}'",
+                missing.name,
+                self.install_name
+            );
         }
-        let excerpt = snippet_for_tokens(&self.body, &self.body_ascii_lower, tokens)
-            .unwrap_or_else(|| self.meta.description.clone());
-        Some(SearchMatch {
-            install_name: self.install_name.clone(),
-            skill_path: self.skill_path.clone(),
-            skill_file: self.skill_file.clone(),
-            meta: self.meta.clone(),
-            score,
-            excerpt,
-        })
+        let placeholder = Regex::new(r"\{\{\s*([A-Za-z0-9_.-]+)\s*\}\}").unwrap();
+        let rendered = placeholder.replace_all(&self.body, |caps: &Captures| {
+            let key = &caps[1];
+            match declared.iter().find(|arg| arg.name == key) {
+                Some(arg) => values
+                    .get(key)
+                    .cloned()
+                    .or_else(|| arg.default.clone())
+                    .unwrap_or_default(),
+                None => caps[0].to_string(),
+            }
+        });
+        Ok(rendered.into_owned())
     }
+}
 
-    pub(crate) fn matches_query(&self, needle: &str) -> bool {
-        self.search_blob.contains(needle)
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PromptArg {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) required: bool,
+    pub(crate) default: Option<String>,
+}
+
+fn yaml_scalar(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
     }
 }
 
@@ -81,138 +143,221 @@ pub(crate) struct SkillDetail {
     pub(crate) skill_path: String,
     pub(crate) skill_file: String,
     pub(crate) body: String,
+    #[serde(flatten)]
+    pub(crate) front_matter: skills::FrontMatterFields,
 }
 
-pub(crate) struct SearchMatch {
-    pub(crate) install_name: String,
-    pub(crate) skill_path: String,
-    pub(crate) skill_file: String,
-    pub(crate) meta: skills::SkillMeta,
-    pub(crate) score: usize,
+pub(crate) struct RankedHit {
+    pub(crate) record: Arc<SkillRecord>,
+    pub(crate) score: f64,
     pub(crate) excerpt: String,
 }
 
-pub(crate) fn scan_skills(project_root: &Path, skills_root: &Path) -> Result<Vec<SkillRecord>> {
-    let mut records = Vec::new();
-    for entry in WalkDir::new(skills_root)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|entry| {
-            entry.file_type().is_file()
-                && entry
-                    .file_name()
-                    .to_string_lossy()
-                    .eq_ignore_ascii_case("SKILL.md")
-        })
-    {
-        let data = match fs::read_to_string(entry.path()) {
-            Ok(data) => data,
-            Err(err) => {
-                eprintln!("warning: unable to read {}: {err}", entry.path().display());
-                continue;
-            }
-        };
-        let meta = match skills::parse_skill_frontmatter_str(&data) {
-            Ok(meta) => meta,
-            Err(err) => {
-                eprintln!(
-                    "warning: unable to parse front-matter for {}: {err}",
-                    entry.path().display()
-                );
-                continue;
+/// The BM25 index over a snapshot of the records, rebuilt lazily after the
+/// catalog changes.
+struct RankedCatalog {
+    records: Vec<Arc<SkillRecord>>,
+    index: search::SearchIndex,
+}
+
+impl RankedCatalog {
+    fn new(records: Vec<Arc<SkillRecord>>) -> Self {
+        let names: Vec<String> = records
+            .iter()
+            .map(|r| format!("{} {}", r.install_name, r.meta.name))
+            .collect();
+        let index = search::SearchIndex::new(records.iter().zip(&names).map(|(r, name)| {
+            search::Document {
+                name,
+                description: &r.meta.description,
+                body: &r.body,
             }
-        };
-        let body = strip_frontmatter(&data).trim().to_string();
-        let body_ascii_lower = body.to_ascii_lowercase();
-        let install_name = entry
-            .path()
-            .parent()
-            .and_then(|p| p.file_name())
-            .and_then(|f| f.to_str())
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| meta.name.clone());
-        let skill_path = relative_path(entry.path().parent().unwrap_or(skills_root), project_root);
-        let skill_file = relative_path(entry.path(), project_root);
-        let mut search_blob = format!(
-            "{}\n{}\n{}\n{}",
-            install_name, meta.name, meta.description, body
-        );
-        search_blob.make_ascii_lowercase();
-        records.push(SkillRecord {
-            install_name,
-            skill_path,
-            skill_file,
-            meta,
-            body,
-            body_ascii_lower,
-            search_blob,
-        });
+        }));
+        RankedCatalog { records, index }
     }
-    records.sort_by(|a, b| a.install_name.cmp(&b.install_name));
-    Ok(records)
 }
 
-pub(crate) fn relative_path(path: &Path, project_root: &Path) -> String {
-    let rel = path.strip_prefix(project_root).unwrap_or(path);
-    rel.to_string_lossy().replace('\\', "/")
+/// In-memory catalog of every SKILL.md under the skills root, keyed by file
+/// path. Built once at startup and patched from watcher events so MCP
+/// requests never walk the tree themselves.
+pub(crate) struct SkillIndex {
+    project_root: PathBuf,
+    skills_root: PathBuf,
+    records: RwLock<BTreeMap<PathBuf, Arc<SkillRecord>>>,
+    ranked: Mutex<Option<Arc<RankedCatalog>>>,
 }
 
-fn strip_frontmatter(text: &str) -> &str {
-    if !text.starts_with("---") {
-        return text;
+impl SkillIndex {
+    pub(crate) fn build(project_root: &Path, skills_root: &Path) -> Self {
+        let index = SkillIndex {
+            project_root: project_root.to_path_buf(),
+            skills_root: skills_root.to_path_buf(),
+            records: RwLock::new(BTreeMap::new()),
+            ranked: Mutex::new(None),
+        };
+        index.rebuild();
+        index
     }
-    let mut offset = match text.find('\n') {
-        Some(idx) => idx + 1,
-        None => return text,
-    };
-    while offset < text.len() {
-        let remainder = &text[offset..];
-        match remainder.find('\n') {
-            Some(rel_end) => {
-                let line = &remainder[..rel_end];
-                if line.trim_end_matches('\r') == "---" {
-                    let mut body = &text[offset + rel_end + 1..];
-                    body = body.strip_prefix('\r').unwrap_or(body);
-                    body = body.strip_prefix('\n').unwrap_or(body);
-                    return body;
-                }
-                offset += rel_end + 1;
+
+    /// Every indexed skill, ordered by install name.
+    pub(crate) fn records(&self) -> Vec<Arc<SkillRecord>> {
+        let mut records: Vec<_> = self.read().values().cloned().collect();
+        records.sort_by(|a, b| a.install_name.cmp(&b.install_name));
+        records
+    }
+
+    pub(crate) fn rebuild(&self) {
+        let fresh = if self.skills_root.exists() {
+            load_records(&self.project_root, &self.skills_root, &self.skills_root)
+        } else {
+            BTreeMap::new()
+        };
+        *self.write() = fresh;
+        self.invalidate_ranking();
+    }
+
+    /// Re-read whatever the changed paths could affect: a SKILL.md refreshes
+    /// its own skill directory, any other path refreshes the subtree it names
+    /// (which also covers whole skill directories being added or removed).
+    pub(crate) fn refresh_paths(&self, paths: &[PathBuf]) {
+        for path in paths {
+            if !path.starts_with(&self.skills_root) {
+                continue;
             }
-            None => {
-                if remainder.trim_end_matches('\r') == "---" {
-                    return "";
+            let scope = if is_skill_file(path) {
+                path.parent().unwrap_or(&self.skills_root)
+            } else {
+                path.as_path()
+            };
+            let fresh = if scope.is_dir() {
+                load_records(&self.project_root, &self.skills_root, scope)
+            } else {
+                BTreeMap::new()
+            };
+            let mut records = self.write();
+            records.retain(|key, _| !key.starts_with(scope));
+            records.extend(fresh);
+        }
+        self.invalidate_ranking();
+    }
+
+    /// Skills matching any query term, ranked by BM25 with the best-matching
+    /// body passage (or the description) as the excerpt.
+    pub(crate) fn search(&self, query: &str) -> Vec<RankedHit> {
+        let ranked = self.ranked();
+        ranked
+            .index
+            .search(query)
+            .into_iter()
+            .map(|hit| {
+                let record = ranked.records[hit.doc].clone();
+                let excerpt = ranked
+                    .index
+                    .best_passage(&record.body, query)
+                    .unwrap_or_else(|| record.meta.description.clone());
+                RankedHit {
+                    record,
+                    score: hit.score,
+                    excerpt,
                 }
-                break;
-            }
+            })
+            .collect()
+    }
+
+    fn ranked(&self) -> Arc<RankedCatalog> {
+        // Built while holding the lock so an invalidation can't be lost to a
+        // concurrent build from older records.
+        let mut ranked = self.ranked.lock().unwrap_or_else(|p| p.into_inner());
+        ranked
+            .get_or_insert_with(|| Arc::new(RankedCatalog::new(self.records())))
+            .clone()
+    }
+
+    fn invalidate_ranking(&self) {
+        *self.ranked.lock().unwrap_or_else(|p| p.into_inner()) = None;
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, BTreeMap<PathBuf, Arc<SkillRecord>>> {
+        self.records
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, BTreeMap<PathBuf, Arc<SkillRecord>>> {
+        self.records
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn is_skill_file(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().eq_ignore_ascii_case("SKILL.md"))
+}
+
+fn load_records(
+    project_root: &Path,
+    skills_root: &Path,
+    scope: &Path,
+) -> BTreeMap<PathBuf, Arc<SkillRecord>> {
+    let mut records = BTreeMap::new();
+    for entry in WalkDir::new(scope)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file() && is_skill_file(entry.path()))
+    {
+        if let Some(record) = load_record(project_root, skills_root, entry.path()) {
+            records.insert(entry.path().to_path_buf(), Arc::new(record));
         }
     }
-    text
+    records
 }
 
-fn snippet_for_tokens<'a>(
-    body: &'a str,
-    body_ascii_lower: &'a str,
-    tokens: &[String],
-) -> Option<String> {
-    for token in tokens {
-        if token.is_empty() {
-            continue;
+fn load_record(project_root: &Path, skills_root: &Path, path: &Path) -> Option<SkillRecord> {
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("warning: unable to read {}: {err}", path.display());
+            return None;
         }
-        if let Some(idx) = body_ascii_lower.find(token) {
-            return Some(snippet_for_range(body, idx, token.len()));
+    };
+    let meta = match skills::parse_skill_frontmatter_str(&data) {
+        Ok(meta) => meta,
+        Err(err) => {
+            eprintln!(
+                "warning: unable to parse front-matter for {}: {err}",
+                path.display()
+            );
+            return None;
         }
-    }
-    None
+    };
+    let body = skills::strip_frontmatter(&data).trim().to_string();
+    let install_name = path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|f| f.to_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| meta.name.clone());
+    let skill_path = relative_path(path.parent().unwrap_or(skills_root), project_root);
+    let skill_file = relative_path(path, project_root);
+    let mut search_blob = format!(
+        "{}\n{}\n{}\n{}",
+        install_name, meta.name, meta.description, body
+    );
+    search_blob.make_ascii_lowercase();
+    Some(SkillRecord {
+        install_name,
+        skill_path,
+        skill_file,
+        meta,
+        body,
+        search_blob,
+    })
 }
 
-fn snippet_for_range(text: &str, idx: usize, token_len: usize) -> String {
-    if text.is_empty() {
-        return String::new();
-    }
-    let start = idx.saturating_sub(80);
-    let end = (idx + token_len + 80).min(text.len());
-    let snippet = text[start..end].trim();
-    snippet.replace('\n', " ")
+pub(crate) fn relative_path(path: &Path, project_root: &Path) -> String {
+    let rel = path.strip_prefix(project_root).unwrap_or(path);
+    rel.to_string_lossy().replace('\\', "/")
 }
 
 #[cfg(test)]
@@ -236,7 +381,7 @@ mod tests {
         write_skill(&skills_root, "alpha", "Alpha skill", "Alpha body");
         write_skill(&skills_root, "beta", "Beta skill", "Use this skill.");
 
-        let records = scan_skills(project.path(), &skills_root).unwrap();
+        let records = SkillIndex::build(project.path(), &skills_root).records();
         assert_eq!(records.len(), 2);
         let first = &records[0];
         assert_eq!(first.install_name, "alpha");
@@ -245,7 +390,7 @@ mod tests {
     }
 
     #[test]
-    fn search_scores_by_tokens() {
+    fn search_ranks_hits_with_passage_excerpts() {
         let project = tempdir().unwrap();
         let skills_root = project.path().join("skills");
         write_skill(
@@ -255,14 +400,70 @@ mod tests {
             "Use bd ready to find issues.",
         );
         write_skill(&skills_root, "sync", "Sync helper", "Sync skills via gh.");
+        write_skill(&skills_root, "ready-check", "Ready checks", "Run checks.");
+
+        let index = SkillIndex::build(project.path(), &skills_root);
+        let hits = index.search("bd ready");
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].record.install_name, "notes");
+        assert!(hits[0].excerpt.contains("bd ready"));
+        assert_eq!(hits[1].record.install_name, "ready-check");
+        assert!(index.search("syncing").len() == 1);
+    }
+
+    #[test]
+    fn index_refreshes_changed_paths() {
+        let project = tempdir().unwrap();
+        let skills_root = project.path().join("skills");
+        write_skill(&skills_root, "alpha", "Alpha skill", "Alpha body");
+        let index = SkillIndex::build(project.path(), &skills_root);
+        assert_eq!(index.records().len(), 1);
 
-        let records = scan_skills(project.path(), &skills_root).unwrap();
-        let query = vec!["bd".to_string(), "ready".to_string()];
-        let hits: Vec<_> = records
+        write_skill(&skills_root, "alpha", "Alpha skill", "Edited body");
+        write_skill(&skills_root, "beta", "Beta skill", "Beta body");
+        index.refresh_paths(&[skills_root.join("alpha/SKILL.md"), skills_root.join("beta")]);
+        let records = index.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].body, "Edited body");
+        assert_eq!(records[1].install_name, "beta");
+
+        std::fs::remove_dir_all(skills_root.join("alpha")).unwrap();
+        index.refresh_paths(&[skills_root.join("alpha")]);
+        let names: Vec<_> = index
+            .records()
             .iter()
-            .filter_map(|skill| skill.score_for_tokens(&query))
+            .map(|r| r.install_name.clone())
             .collect();
-        assert_eq!(hits.len(), 1);
-        assert!(hits[0].excerpt.contains("bd ready"));
+        assert_eq!(names, vec!["beta"]);
+    }
+
+    #[test]
+    fn renders_prompt_arguments_from_front_matter() {
+        let project = tempdir().unwrap();
+        let skills_root = project.path().join("skills");
+        let skill_dir = skills_root.join("review");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: review\ndescription: Review code\narguments:\n  - name: focus\n    description: What to look at\n    required: true\n  - name: tone\n    default: terse\n  - audience\n---\nReview {{ focus }} in a {{tone}} voice for {{audience}}. Keep {{other}}.\n",
+        )
+        .unwrap();
+
+        let records = SkillIndex::build(project.path(), &skills_root).records();
+        let record = &records[0];
+        let args = record.prompt_arguments();
+        assert_eq!(args.len(), 3);
+        assert!(args[0].required);
+        assert_eq!(args[0].description.as_deref(), Some("What to look at"));
+        assert_eq!(args[1].default.as_deref(), Some("terse"));
+        assert_eq!(args[2].name, "audience");
+        assert!(!args[2].required);
+
+        assert!(record.render_prompt(&BTreeMap::new()).is_err());
+        let values = BTreeMap::from([("focus".to_string(), "error handling".to_string())]);
+        assert_eq!(
+            record.render_prompt(&values).unwrap(),
+            "Review error handling in a terse voice for . Keep {{other}}."
+        );
     }
 }
@@ -0,0 +1,173 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Files bundled next to SKILL.md (scripts, references, templates), as
+/// `/`-separated paths relative to the skill directory. This is exactly the
+/// set [`resolve_skill_file`] will serve: dotfiles and symlinks are listed
+/// as long as they resolve to a file inside the skill.
+pub(crate) fn list_skill_files(skill_dir: &Path) -> Vec<String> {
+    let mut files: Vec<String> = WalkDir::new(skill_dir)
+        .min_depth(1)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let rel = entry.path().strip_prefix(skill_dir).ok()?;
+            Some(rel.to_string_lossy().replace('\\', "/"))
+        })
+        .filter(|rel| !rel.eq_ignore_ascii_case("SKILL.md"))
+        .filter(|rel| resolve_skill_file(skill_dir, rel).is_ok())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Resolve `rel` inside `skill_dir`, refusing anything that would land
+/// outside it (absolute paths, `..`, or symlinks pointing elsewhere).
+pub(crate) fn resolve_skill_file(skill_dir: &Path, rel: &str) -> Result<PathBuf> {
+    let rel_path = Path::new(rel);
+    if rel.is_empty()
+        || rel_path
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        bail!("invalid skill file path '{rel}'");
+    }
+    let root =
+        fs::canonicalize(skill_dir).with_context(|| format!("resolve {}", skill_dir.display()))?;
+    let path = fs::canonicalize(root.join(rel_path))
+        .with_context(|| format!("skill file '{rel}' not found"))?;
+    if !path.starts_with(&root) {
+        bail!("skill file '{rel}' resolves outside the skill directory");
+    }
+    if !path.is_file() {
+        bail!("skill file '{rel}' is not a file");
+    }
+    Ok(path)
+}
+
+pub(crate) enum FileContents {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+/// Read a bundled file, keeping it as text only when it is valid UTF-8 and
+/// its MIME type is textual.
+pub(crate) fn read_skill_file(path: &Path) -> Result<(FileContents, &'static str)> {
+    let bytes = fs::read(path).with_context(|| format!("read {}", path.display()))?;
+    let mime = mime_for(path);
+    let textual = mime.starts_with("text/")
+        || matches!(
+            mime,
+            "application/json" | "application/toml" | "application/yaml" | "image/svg+xml"
+        );
+    if textual {
+        if let Ok(text) = String::from_utf8(bytes.clone()) {
+            return Ok((FileContents::Text(text), mime));
+        }
+    } else if mime == "application/octet-stream" {
+        // Unknown extension: fall back to sniffing for UTF-8 so extensionless
+        // scripts still come back as text.
+        if let Ok(text) = String::from_utf8(bytes.clone()) {
+            return Ok((FileContents::Text(text), "text/plain"));
+        }
+    }
+    Ok((FileContents::Binary(bytes), mime))
+}
+
+pub(crate) fn mime_for(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "md" | "markdown" => "text/markdown",
+        "txt" | "text" => "text/plain",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "csv" => "text/csv",
+        "xml" => "text/xml",
+        "sh" | "bash" | "zsh" => "text/x-shellscript",
+        "py" => "text/x-python",
+        "rs" => "text/x-rust",
+        "js" | "mjs" | "cjs" => "text/javascript",
+        "ts" => "text/x-typescript",
+        "rb" => "text/x-ruby",
+        "go" => "text/x-go",
+        "json" => "application/json",
+        "toml" => "application/toml",
+        "yaml" | "yml" => "application/yaml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" | "tgz" => "application/gzip",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn lists_bundled_files_and_confines_paths() {
+        let dir = tempdir().unwrap();
+        let skill = dir.path().join("skill");
+        fs::create_dir_all(skill.join("scripts")).unwrap();
+        fs::create_dir_all(skill.join(".hidden")).unwrap();
+        fs::write(skill.join("SKILL.md"), "---\nname: s\n---\n").unwrap();
+        fs::write(skill.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+        fs::write(skill.join(".hidden/x"), "x").unwrap();
+        fs::write(dir.path().join("secret.txt"), "nope").unwrap();
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("scripts/run.sh", skill.join("run")).unwrap();
+            std::os::unix::fs::symlink("../secret.txt", skill.join("leak.txt")).unwrap();
+        }
+
+        let listed = list_skill_files(&skill);
+        for rel in &listed {
+            assert!(resolve_skill_file(&skill, rel).is_ok(), "{rel}");
+        }
+        assert!(listed.contains(&".hidden/x".to_string()));
+        assert!(listed.contains(&"scripts/run.sh".to_string()));
+        #[cfg(unix)]
+        {
+            assert!(listed.contains(&"run".to_string()));
+            assert!(!listed.contains(&"leak.txt".to_string()));
+            assert!(resolve_skill_file(&skill, "leak.txt").is_err());
+        }
+        assert!(resolve_skill_file(&skill, "scripts/run.sh").is_ok());
+        assert!(resolve_skill_file(&skill, "../secret.txt").is_err());
+        assert!(resolve_skill_file(&skill, "/etc/passwd").is_err());
+        assert!(resolve_skill_file(&skill, "scripts").is_err());
+    }
+
+    #[test]
+    fn binary_files_come_back_as_blobs() {
+        let dir = tempdir().unwrap();
+        let png = dir.path().join("logo.png");
+        fs::write(&png, [0x89, b'P', b'N', b'G', 0, 0xff]).unwrap();
+        let (contents, mime) = read_skill_file(&png).unwrap();
+        assert_eq!(mime, "image/png");
+        assert!(matches!(contents, FileContents::Binary(_)));
+
+        let script = dir.path().join("run");
+        fs::write(&script, "echo hi\n").unwrap();
+        let (contents, mime) = read_skill_file(&script).unwrap();
+        assert_eq!(mime, "text/plain");
+        assert!(matches!(contents, FileContents::Text(_)));
+    }
+}
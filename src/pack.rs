@@ -0,0 +1,108 @@
+use crate::{config, digest, git, install, local, paths, skills, template};
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+pub struct PackArgs<'a> {
+    pub installed_name: &'a str,
+    pub out: Option<&'a str>,
+    pub root: Option<&'a str>,
+}
+
+pub struct UnpackArgs<'a> {
+    pub archive: &'a str,
+    pub alias: Option<&'a str>,
+    pub root: Option<&'a str>,
+}
+
+/// Zip an installed skill the way Claude.ai and the API expect it: one
+/// top-level folder named after the skill. Entries are sorted and carry a fixed
+/// timestamp, so packing the same tree twice yields identical bytes.
+pub fn run_pack(args: PackArgs) -> Result<()> {
+    let project_root = git::ensure_git_repo()?;
+    let cfg = config::load_or_default()?;
+    let install_root_rel = args.root.unwrap_or(&cfg.default_root);
+    let install_root = paths::resolve_project_path(&project_root, install_root_rel);
+    let dir = install_root.join(args.installed_name);
+    if !dir.is_dir() {
+        bail!(
+            "skill '{}' not found under {}",
+            args.installed_name,
+            install_root.display()
+        );
+    }
+
+    let meta = skills::parse_frontmatter_file(&dir.join("SKILL.md"))
+        .with_context(|| format!("invalid SKILL.md in '{}'", args.installed_name))?;
+    template::validate_skill_name(&meta.name)
+        .with_context(|| format!("invalid name in '{}'", args.installed_name))?;
+    if meta.description.trim().is_empty() {
+        bail!(
+            "SKILL.md in '{}' has no description; Claude rejects skills without one",
+            args.installed_name
+        );
+    }
+
+    let out = match args.out {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(format!("{}.zip", args.installed_name)),
+    };
+    let count = write_archive(&dir, &meta.name, &out)?;
+    println!(
+        "Packed '{}' ({count} file(s)) to {}",
+        args.installed_name,
+        out.display()
+    );
+    Ok(())
+}
+
+fn write_archive(dir: &Path, top: &str, out: &Path) -> Result<usize> {
+    let entries = digest::manifest_dir(dir)?;
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let base = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .last_modified_time(zip::DateTime::default());
+    zip.add_directory(format!("{top}/"), base.unix_permissions(0o755))?;
+    for entry in &entries {
+        let name = format!("{top}/{}", entry.path);
+        let path = dir.join(&entry.path);
+        if entry.mode == "120000" {
+            let target =
+                fs::read_link(&path).with_context(|| format!("readlink {}", path.display()))?;
+            zip.add_symlink(name, target.to_string_lossy().replace('\\', "/"), base)?;
+            continue;
+        }
+        let perms = if entry.mode == "100755" { 0o755 } else { 0o644 };
+        zip.start_file(name, base.unix_permissions(perms))?;
+        let data = fs::read(&path).with_context(|| format!("read {}", path.display()))?;
+        zip.write_all(&data)?;
+    }
+    let buf = zip.finish()?.into_inner();
+    if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(out, buf).with_context(|| format!("write {}", out.display()))?;
+    Ok(entries.len())
+}
+
+/// Install a skill from a Claude-style zip and record it in the lockfile as a
+/// local source.
+pub fn run_unpack(args: UnpackArgs) -> Result<()> {
+    let path = Path::new(args.archive);
+    if !local::is_zip(path) || !path.is_file() {
+        bail!("'{}' is not a .zip archive", args.archive);
+    }
+    install::run_install(install::InstallArgs {
+        repo: args.archive,
+        skill_name: None,
+        all: false,
+        alias: args.alias,
+        path: None,
+        root: args.root,
+        https: false,
+        git_ref: None,
+        constraint: None,
+    })
+}
@@ -1,4 +1,4 @@
-use crate::{git, lock};
+use crate::{config, git, local, lock, paths};
 use anyhow::{bail, Result};
 use gix_url as gurl;
 
@@ -10,9 +10,17 @@ pub fn run_precommit(allow_local: bool) -> Result<()> {
         return Ok(());
     }
     let lf = lock::Lockfile::load(&lock_path)?;
+    warn_on_unlocked_edits(&project_root, &lf)?;
 
     let mut local_entries: Vec<String> = vec![];
     for s in &lf.skills {
+        if let Some(path) = s.source.local_path() {
+            // Imports from inside the project travel with it; anything else is machine-specific.
+            if !local::is_shared_origin(path) {
+                local_entries.push(format!("{} -> {} (imported)", s.install_name, path));
+            }
+            continue;
+        }
         let spec = s.source.repo_spec();
         let url = spec.url.as_str();
         let is_local = is_local_source(url, &spec.host);
@@ -34,7 +42,7 @@ pub fn run_precommit(allow_local: bool) -> Result<()> {
             eprintln!("  - {e}");
         }
         eprintln!(
-            "These entries will not be usable by collaborators. Replace with ssh/https URLs, or run with --allow-local to bypass."
+            "These entries will not be usable by collaborators. Replace with ssh/https URLs or import from a path inside the project, or run with --allow-local to bypass."
         );
         if !allow_local {
             bail!("local sources present; failing precommit");
@@ -43,6 +51,37 @@ pub fn run_precommit(allow_local: bool) -> Result<()> {
     Ok(())
 }
 
+/// Warn (without failing) when installed files drift from the locked manifest,
+/// so edits don't get committed without a matching `sk sync-back`.
+fn warn_on_unlocked_edits(project_root: &std::path::Path, lf: &lock::Lockfile) -> Result<()> {
+    let cfg = config::load_or_default()?;
+    let install_root = paths::resolve_project_path(project_root, &cfg.default_root);
+    let mut drifted: Vec<(String, Vec<String>)> = Vec::new();
+    for s in &lf.skills {
+        let dest = install_root.join(&s.install_name);
+        if !dest.exists() {
+            continue;
+        }
+        if let Some(changes) = s.manifest_changes(&dest) {
+            if !changes.is_empty() {
+                drifted.push((s.install_name.clone(), changes.describe()));
+            }
+        }
+    }
+    if drifted.is_empty() {
+        return Ok(());
+    }
+    eprintln!("sk precommit: installed skills differ from skills.lock.json:");
+    for (name, lines) in &drifted {
+        eprintln!("  - {name}");
+        for line in lines {
+            eprintln!("      {line}");
+        }
+    }
+    eprintln!("Run 'sk sync-back <name>' to publish these edits, or revert them.");
+    Ok(())
+}
+
 fn is_local_source(url: &str, host_field: &str) -> bool {
     if host_field == "local" {
         return true;
@@ -1,4 +1,4 @@
-use crate::{config, digest, git, lock, paths};
+use crate::{config, deps, digest, git, lock, paths};
 use anyhow::{anyhow, bail, Context, Result};
 use chrono::Utc;
 use std::fs;
@@ -20,6 +20,18 @@ pub fn run_remove(args: RemoveArgs) -> Result<()> {
         bail!("no lockfile found");
     }
 
+    if !args.force {
+        let dependents =
+            deps::dependents_of(&deps::scan_installed(&install_root), args.installed_name);
+        if !dependents.is_empty() {
+            bail!(
+                "'{}' is required by: {}. Refusing to remove. Use --force to override.",
+                args.installed_name,
+                dependents.join(", ")
+            );
+        }
+    }
+
     let removed_name = lock::edit_lockfile(&lock_path, |lf| {
         let idx = lf
             .skills
@@ -34,7 +46,7 @@ pub fn run_remove(args: RemoveArgs) -> Result<()> {
                 entry.install_name
             );
         }
-        let digest_result = digest::digest_dir(&dest);
+        let digest_result = digest::digest_dir_versioned(&dest, lf.digest_version);
         let is_modified = match digest_result {
             Ok(cur) => cur != entry.digest,
             Err(e) => {
@@ -46,8 +58,18 @@ pub fn run_remove(args: RemoveArgs) -> Result<()> {
             }
         };
         if is_modified && !args.force {
+            let changed = entry
+                .manifest_changes(&dest)
+                .map(|changes| {
+                    changes
+                        .describe()
+                        .iter()
+                        .map(|line| format!("\n  {line}"))
+                        .collect::<String>()
+                })
+                .unwrap_or_default();
             bail!(
-                "Local edits detected in '{}'. Refusing to remove. Use --force to override.",
+                "Local edits detected in '{}'. Refusing to remove. Use --force to override.{changed}",
                 dest.display()
             );
         }
@@ -1,4 +1,4 @@
-use crate::{config, git, lock};
+use crate::{config, git, lock, search, skills};
 use anyhow::{bail, Result};
 use serde::Serialize;
 
@@ -15,7 +15,6 @@ pub struct RepoSearchArgs<'a> {
 struct SearchSetup {
     list_mode: bool,
     trimmed_query: Option<String>,
-    lowered_query: Option<String>,
     targets: Vec<(String, git::RepoSpec)>,
 }
 
@@ -24,6 +23,8 @@ struct CatalogEntry {
     name: String,
     description: String,
     path: String,
+    #[serde(flatten)]
+    front_matter: skills::FrontMatterFields,
 }
 
 #[derive(Serialize)]
@@ -32,6 +33,8 @@ struct SearchHit {
     name: String,
     description: String,
     path: String,
+    #[serde(flatten)]
+    front_matter: skills::FrontMatterFields,
 }
 
 pub fn run_repo_search(args: RepoSearchArgs<'_>) -> Result<()> {
@@ -78,7 +81,6 @@ fn build_search_setup(
         );
     }
     let list_mode = args.list_all || trimmed_query.is_none();
-    let lowered_query = trimmed_query.as_ref().map(|s| s.to_lowercase());
 
     let targets: Vec<(String, git::RepoSpec)> = if let Some(target) = args.target {
         let spec = super::resolve_target_spec(target, lockfile, cfg, args.https)?;
@@ -104,7 +106,6 @@ fn build_search_setup(
     Ok(SearchSetup {
         list_mode,
         trimmed_query,
-        lowered_query,
         targets,
     })
 }
@@ -119,6 +120,7 @@ fn print_repo_skill_listing(args: &RepoSearchArgs<'_>, spec: &git::RepoSpec) ->
                 name: skill.meta.name.clone(),
                 description: skill.meta.description.clone(),
                 path: skill.skill_path.clone(),
+                front_matter: skill.meta.front_matter(),
             })
             .collect();
         println!("{}", serde_json::to_string_pretty(&entries)?);
@@ -138,28 +140,45 @@ fn print_repo_skill_listing(args: &RepoSearchArgs<'_>, spec: &git::RepoSpec) ->
 }
 
 fn collect_search_hits(setup: &SearchSetup) -> Result<Vec<SearchHit>> {
-    let mut hits = Vec::new();
+    let mut candidates = Vec::new();
     for (label, spec) in &setup.targets {
         let snapshot = load_repo_snapshot(spec)?;
         for skill in snapshot.skills.iter() {
-            let include = if setup.list_mode {
-                true
-            } else if let Some(needle) = setup.lowered_query.as_ref() {
-                matches_query(needle, skill)
-            } else {
-                false
-            };
-            if include {
-                hits.push(SearchHit {
-                    repo: label.clone(),
-                    name: skill.meta.name.clone(),
-                    description: skill.meta.description.clone(),
-                    path: skill.skill_path.clone(),
-                });
-            }
+            candidates.push((label, skill.clone()));
         }
     }
-    Ok(hits)
+    let to_hit = |(label, skill): &(&String, skills::DiscoveredSkill)| SearchHit {
+        repo: (*label).clone(),
+        name: skill.meta.name.clone(),
+        description: skill.meta.description.clone(),
+        path: skill.skill_path.clone(),
+        front_matter: skill.meta.front_matter(),
+    };
+    let query = match setup.trimmed_query.as_deref() {
+        Some(query) if !setup.list_mode => query,
+        _ => return Ok(candidates.iter().map(to_hit).collect()),
+    };
+    // Repo listings carry no SKILL.md body, so the path stands in for it.
+    let index = search::SearchIndex::new(candidates.iter().map(|(_, skill)| search::Document {
+        name: &skill.meta.name,
+        description: &skill.meta.description,
+        body: &skill.skill_path,
+    }));
+    let ranked = index.search(query);
+    if ranked.is_empty() {
+        // Stemmed terms only match whole words; keep partial-word queries
+        // (`guide`, a path fragment) working with the plain substring test.
+        let needle = query.to_lowercase();
+        return Ok(candidates
+            .iter()
+            .filter(|(_, skill)| matches_query(&needle, skill))
+            .map(to_hit)
+            .collect());
+    }
+    Ok(ranked
+        .into_iter()
+        .map(|hit| to_hit(&candidates[hit.doc]))
+        .collect())
 }
 
 fn display_search_hits(
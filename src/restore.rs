@@ -0,0 +1,203 @@
+use crate::{config, digest, git, install, local, lock, paths};
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+pub struct RestoreArgs<'a> {
+    pub root: Option<&'a str>,
+    pub force: bool,
+}
+
+/// A skill whose locked tree has been extracted and verified, waiting to be
+/// moved into place.
+struct Staged<'a> {
+    skill: &'a lock::LockSkill,
+    staged: PathBuf,
+    dest: PathBuf,
+    replacing: bool,
+}
+
+/// Rebuild every install recorded in skills.lock.json from its locked commit
+/// (or, for local imports, from the recorded directory or zip).
+/// Every skill is extracted into a staging dir and checked against its locked
+/// digest first; installs are only touched once all of them verify, so any
+/// mismatch fails the whole run without changing anything.
+pub fn run_restore(args: RestoreArgs) -> Result<()> {
+    let project_root = git::ensure_git_repo()?;
+    let cfg = config::load_or_default()?;
+    let install_root_rel = args.root.unwrap_or(&cfg.default_root);
+    let install_root = paths::resolve_project_path(&project_root, install_root_rel);
+    let lock_path = project_root.join("skills.lock.json");
+    if !lock_path.exists() {
+        bail!("no lockfile found");
+    }
+    let lockfile = lock::Lockfile::load(&lock_path)?;
+    if lockfile.skills.is_empty() {
+        println!("No skills in lockfile.");
+        return Ok(());
+    }
+    fs::create_dir_all(&install_root)?;
+    let staging = TempDir::new_in(&project_root).context("create staging dir")?;
+    let ctx = RestoreContext {
+        project_root: &project_root,
+        staging_root: staging.path(),
+        digest_version: lockfile.digest_version,
+        force: args.force,
+    };
+
+    let mut refreshed: HashSet<PathBuf> = HashSet::new();
+    let mut failures = Vec::new();
+    let mut ready = Vec::new();
+    for skill in &lockfile.skills {
+        let dest = install_root.join(&skill.install_name);
+        match stage_skill(skill, dest, &ctx, &mut refreshed) {
+            Ok(Some(staged)) => ready.push(staged),
+            Ok(None) => {}
+            Err(err) => {
+                eprintln!("{}: {:#}", skill.install_name, err);
+                failures.push(skill.install_name.clone());
+            }
+        }
+    }
+    if !failures.is_empty() {
+        bail!(
+            "restore failed for {} skill(s): {}; no installs were changed",
+            failures.len(),
+            failures.join(", ")
+        );
+    }
+
+    for staged in &ready {
+        move_into_place(staged)?;
+        let verb = if staged.replacing {
+            "Replaced modified"
+        } else {
+            "Restored"
+        };
+        println!(
+            "{verb} '{}' {}",
+            staged.skill.install_name,
+            origin_label(staged.skill)
+        );
+    }
+    println!(
+        "Restore complete: {} restored, {} already up to date.",
+        ready.len(),
+        lockfile.skills.len() - ready.len()
+    );
+    Ok(())
+}
+
+struct RestoreContext<'a> {
+    project_root: &'a Path,
+    staging_root: &'a Path,
+    digest_version: u32,
+    force: bool,
+}
+
+/// Extract `skill` into the staging dir and verify its digest. Returns `None`
+/// when the install already matches the lockfile.
+fn stage_skill<'a>(
+    skill: &'a lock::LockSkill,
+    dest: PathBuf,
+    ctx: &RestoreContext,
+    refreshed: &mut HashSet<PathBuf>,
+) -> Result<Option<Staged<'a>>> {
+    let replacing = dest.exists();
+    if replacing {
+        let current = digest::digest_dir_versioned(&dest, ctx.digest_version).ok();
+        if current.as_deref() == Some(skill.digest.as_str()) {
+            return Ok(None);
+        }
+        if !ctx.force {
+            bail!(
+                "installed dir {} does not match the locked digest. Use --force to replace it.",
+                dest.display()
+            );
+        }
+    }
+
+    let staged = ctx.staging_root.join(&skill.install_name);
+    match skill.source.local_path() {
+        Some(stored) => {
+            let origin = local::resolve_origin(ctx.project_root, stored);
+            local::import(&origin, skill.source.skill_path(), &staged)?;
+        }
+        None => extract_locked_commit(skill, &staged, refreshed)?,
+    }
+    let staged_digest = digest::digest_dir_versioned(&staged, ctx.digest_version)?;
+    if staged_digest != skill.digest {
+        bail!(
+            "digest mismatch {}: lockfile has {}, extracted tree has {}",
+            origin_label(skill),
+            skill.digest,
+            staged_digest
+        );
+    }
+    Ok(Some(Staged {
+        skill,
+        staged,
+        dest,
+        replacing,
+    }))
+}
+
+fn move_into_place(staged: &Staged) -> Result<()> {
+    let Staged {
+        staged: from,
+        dest,
+        replacing,
+        ..
+    } = staged;
+    if *replacing {
+        fs::remove_dir_all(dest).with_context(|| format!("remove {}", dest.display()))?;
+    }
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(from, dest)
+        .with_context(|| format!("rename {} -> {}", from.display(), dest.display()))
+}
+
+fn extract_locked_commit(
+    skill: &lock::LockSkill,
+    staged: &Path,
+    refreshed: &mut HashSet<PathBuf>,
+) -> Result<()> {
+    let spec = skill.source.repo_spec_owned();
+    let cache_dir =
+        paths::resolve_or_primary_cache_path(&spec.url, &spec.host, &spec.owner, &spec.repo);
+    let has_commit = cache_dir.exists() && git::has_object(&cache_dir, &skill.commit)?;
+    if !has_commit && refreshed.insert(cache_dir.clone()) {
+        git::ensure_cached_repo(&cache_dir, &spec)
+            .with_context(|| format!("refreshing cache for {}/{}", spec.owner, spec.repo))?;
+    }
+    if !git::has_object(&cache_dir, &skill.commit)? {
+        bail!(
+            "locked commit {} not found in {}/{} (force-push?)",
+            short(&skill.commit),
+            spec.owner,
+            spec.repo
+        );
+    }
+
+    install::extract_subdir_from_commit(
+        &cache_dir,
+        &skill.commit,
+        skill.source.skill_path(),
+        staged,
+    )
+}
+
+fn origin_label(skill: &lock::LockSkill) -> String {
+    match skill.source.local_path() {
+        Some(path) => format!("from {path}"),
+        None => format!("@ {}", short(&skill.commit)),
+    }
+}
+
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
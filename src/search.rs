@@ -0,0 +1,395 @@
+//! Ranked skill search shared by the MCP `skills_search` tool and
+//! `sk repo search`: BM25 over name, description, and body, with the name and
+//! description weighted above the body. Also home to the typo-tolerant
+//! "did you mean" suggestions used when a skill name doesn't resolve.
+
+use std::collections::{HashMap, HashSet};
+
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+const EXCERPT_CHARS: usize = 240;
+const MAX_SUGGESTIONS: usize = 5;
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Name,
+    Description,
+    Body,
+}
+
+impl Field {
+    const ALL: [Field; 3] = [Field::Name, Field::Description, Field::Body];
+
+    fn weight(self) -> f64 {
+        match self {
+            Field::Name => 3.0,
+            Field::Description => 2.0,
+            Field::Body => 1.0,
+        }
+    }
+
+    fn slot(self) -> usize {
+        self as usize
+    }
+}
+
+/// The searchable text of one skill.
+pub struct Document<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    pub body: &'a str,
+}
+
+impl Document<'_> {
+    fn field(&self, field: Field) -> &str {
+        match field {
+            Field::Name => self.name,
+            Field::Description => self.description,
+            Field::Body => self.body,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    /// Position of the matching document in the order it was indexed.
+    pub doc: usize,
+    pub score: f64,
+}
+
+struct IndexedDoc {
+    term_freqs: [HashMap<String, u32>; 3],
+    lengths: [usize; 3],
+}
+
+pub struct SearchIndex {
+    docs: Vec<IndexedDoc>,
+    doc_freq: HashMap<String, usize>,
+    avg_lengths: [f64; 3],
+}
+
+impl SearchIndex {
+    pub fn new<'a>(documents: impl IntoIterator<Item = Document<'a>>) -> Self {
+        let mut docs = Vec::new();
+        let mut doc_freq: HashMap<String, usize> = HashMap::new();
+        let mut total_lengths = [0usize; 3];
+        for document in documents {
+            let mut term_freqs: [HashMap<String, u32>; 3] = Default::default();
+            let mut lengths = [0usize; 3];
+            let mut seen = HashSet::new();
+            for field in Field::ALL {
+                let terms = tokenize(document.field(field));
+                lengths[field.slot()] = terms.len();
+                total_lengths[field.slot()] += terms.len();
+                for term in terms {
+                    *term_freqs[field.slot()].entry(term.clone()).or_default() += 1;
+                    seen.insert(term);
+                }
+            }
+            for term in seen {
+                *doc_freq.entry(term).or_default() += 1;
+            }
+            docs.push(IndexedDoc {
+                term_freqs,
+                lengths,
+            });
+        }
+        let count = docs.len().max(1) as f64;
+        let avg_lengths = total_lengths.map(|total| (total as f64 / count).max(1.0));
+        SearchIndex {
+            docs,
+            doc_freq,
+            avg_lengths,
+        }
+    }
+
+    /// Every document matching at least one query term, best first. Ties keep
+    /// index order so callers can pre-sort for a stable fallback.
+    pub fn search(&self, query: &str) -> Vec<Hit> {
+        let terms = unique_terms(query);
+        let mut hits: Vec<Hit> = self
+            .docs
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, doc)| {
+                let score: f64 = terms.iter().map(|term| self.term_score(doc, term)).sum();
+                (score > 0.0).then_some(Hit { doc: idx, score })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.doc.cmp(&b.doc)));
+        hits
+    }
+
+    /// The paragraph (or line, for long paragraphs) of `body` that best covers
+    /// the query, flattened onto one line and trimmed to excerpt length.
+    pub fn best_passage(&self, body: &str, query: &str) -> Option<String> {
+        let terms = unique_terms(query);
+        let mut best: Option<(f64, &str)> = None;
+        for passage in passages(body) {
+            let present: HashSet<String> = tokenize(passage).into_iter().collect();
+            let score: f64 = terms
+                .iter()
+                .filter(|term| present.contains(*term))
+                .map(|term| self.idf(term))
+                .sum();
+            if score > 0.0 && best.is_none_or(|(top, _)| score > top) {
+                best = Some((score, passage));
+            }
+        }
+        best.map(|(_, passage)| excerpt(passage))
+    }
+
+    fn idf(&self, term: &str) -> f64 {
+        let n = self.docs.len() as f64;
+        let df = self.doc_freq.get(term).copied().unwrap_or(0) as f64;
+        (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+    }
+
+    /// BM25F: per-field frequencies are length-normalized and weighted, then
+    /// saturated once so a term repeated across fields isn't counted twice.
+    fn term_score(&self, doc: &IndexedDoc, term: &str) -> f64 {
+        let weighted: f64 = Field::ALL
+            .iter()
+            .map(|field| {
+                let slot = field.slot();
+                let tf = doc.term_freqs[slot].get(term).copied().unwrap_or(0) as f64;
+                if tf == 0.0 {
+                    return 0.0;
+                }
+                let norm = 1.0 - B + B * doc.lengths[slot] as f64 / self.avg_lengths[slot];
+                field.weight() * tf / norm
+            })
+            .sum();
+        if weighted == 0.0 {
+            return 0.0;
+        }
+        self.idf(term) * weighted * (K1 + 1.0) / (weighted + K1)
+    }
+}
+
+/// Lowercased, stemmed terms. Hyphens and underscores split words, and a
+/// joined form is added for compounds so `pdf-tools`, `pdf_tools`, and
+/// `pdftools` all meet.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for chunk in text.split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_')) {
+        let parts: Vec<String> = chunk
+            .split(['-', '_'])
+            .filter(|part| !part.is_empty())
+            .map(|part| part.to_lowercase())
+            .collect();
+        if parts.len() > 1 {
+            terms.push(stem(&parts.concat()));
+        }
+        terms.extend(parts.iter().map(|part| stem(part)));
+    }
+    terms
+}
+
+fn unique_terms(query: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    tokenize(query)
+        .into_iter()
+        .filter(|term| seen.insert(term.clone()))
+        .collect()
+}
+
+/// A light suffix stripper: enough to fold plurals, -ing, -ed, and a trailing
+/// -e together without pulling in a full Porter implementation.
+pub fn stem(word: &str) -> String {
+    if word.chars().count() <= 3 || !word.is_ascii() {
+        return word.to_string();
+    }
+    let mut w = word.to_string();
+    if let Some(base) = w.strip_suffix("ies").filter(|b| b.len() >= 2) {
+        w = format!("{base}y");
+    } else if w.ends_with("sses") {
+        w.truncate(w.len() - 2);
+    } else if let Some(base) = w
+        .strip_suffix("es")
+        .filter(|b| b.len() >= 3 && ["s", "x", "z", "ch", "sh"].iter().any(|s| b.ends_with(s)))
+    {
+        w = base.to_string();
+    } else if w.ends_with('s') && !w.ends_with("ss") && !w.ends_with("us") && !w.ends_with("is") {
+        w.pop();
+    }
+    for suffix in ["ing", "ed"] {
+        if let Some(base) = w.strip_suffix(suffix).filter(|b| b.len() >= 3) {
+            let base = base.to_string();
+            let bytes = base.as_bytes();
+            let n = bytes.len();
+            w = if n >= 2 && bytes[n - 1] == bytes[n - 2] && !b"lsz".contains(&bytes[n - 1]) {
+                base[..n - 1].to_string()
+            } else {
+                base
+            };
+            break;
+        }
+    }
+    if w.len() > 4 && w.ends_with('e') {
+        w.pop();
+    }
+    w
+}
+
+fn passages(body: &str) -> impl Iterator<Item = &str> {
+    body.split("\n\n")
+        .flat_map(|para| {
+            if para.len() > EXCERPT_CHARS * 2 {
+                para.lines().collect::<Vec<_>>()
+            } else {
+                vec![para]
+            }
+        })
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+}
+
+fn excerpt(passage: &str) -> String {
+    let flat = passage.split_whitespace().collect::<Vec<_>>().join(" ");
+    if flat.chars().count() <= EXCERPT_CHARS {
+        return flat;
+    }
+    let cut: String = flat.chars().take(EXCERPT_CHARS).collect();
+    match cut.rfind(' ') {
+        Some(idx) => format!("{}…", &cut[..idx]),
+        None => format!("{cut}…"),
+    }
+}
+
+/// Names from `candidates` close enough to `needle` to be what the user
+/// meant, best first: a small edit distance, one containing the other, or a
+/// shared word once "skill" itself is set aside.
+pub fn suggest<'a>(needle: &str, candidates: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let wanted = normalize_name(needle);
+    if wanted.is_empty() {
+        return Vec::new();
+    }
+    let wanted_words = name_words(&wanted);
+    let mut scored: Vec<(f64, &str)> = Vec::new();
+    for candidate in candidates {
+        let name = normalize_name(candidate);
+        if name.is_empty() || scored.iter().any(|(_, seen)| *seen == candidate) {
+            continue;
+        }
+        let shortest = wanted.chars().count().min(name.chars().count());
+        let close = strsim::damerau_levenshtein(&wanted, &name) <= (shortest / 3).max(1);
+        let contains = shortest >= 3 && (name.contains(&wanted) || wanted.contains(&name));
+        let shares_word = name_words(&name).iter().any(|w| wanted_words.contains(w));
+        if close || contains || shares_word {
+            scored.push((strsim::jaro_winkler(&wanted, &name), candidate));
+        }
+    }
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(b.1)));
+    scored.truncate(MAX_SUGGESTIONS);
+    scored.into_iter().map(|(_, name)| name).collect()
+}
+
+/// `suggest` rendered as a sentence to append to a not-found error, or an
+/// empty string when nothing is close.
+pub fn did_you_mean<'a>(needle: &str, candidates: impl IntoIterator<Item = &'a str>) -> String {
+    let names: Vec<String> = suggest(needle, candidates)
+        .into_iter()
+        .map(|name| format!("'{name}'"))
+        .collect();
+    if names.is_empty() {
+        String::new()
+    } else {
+        format!(" Did you mean {}?", names.join(", "))
+    }
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .replace(['_', ' '], "-")
+        .trim_matches('-')
+        .to_string()
+}
+
+fn name_words(name: &str) -> HashSet<String> {
+    name.split('-')
+        .map(stem)
+        .filter(|w| w.len() >= 2 && w != "skill")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stems_common_suffixes_together() {
+        for (a, b) in [
+            ("tests", "testing"),
+            ("caching", "caches"),
+            ("release", "released"),
+            ("running", "runs"),
+            ("policies", "policy"),
+        ] {
+            assert_eq!(stem(a), stem(b), "{a} vs {b}");
+        }
+    }
+
+    #[test]
+    fn hyphens_underscores_and_compounds_meet() {
+        let a = tokenize("pdf-tools");
+        let b = tokenize("pdf_tools");
+        assert_eq!(a, b);
+        assert!(tokenize("pdftools").iter().all(|t| a.contains(t)));
+    }
+
+    #[test]
+    fn name_and_description_outrank_body() {
+        let index = SearchIndex::new([
+            Document {
+                name: "notes",
+                description: "Keep notes",
+                body: "Mentions deploy once in passing.",
+            },
+            Document {
+                name: "deploy-helper",
+                description: "Deploy services safely",
+                body: "Step by step.",
+            },
+            Document {
+                name: "unrelated",
+                description: "Nothing here",
+                body: "Nothing here either.",
+            },
+        ]);
+        let hits = index.search("deploying");
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].doc, 1);
+        assert!(hits[0].score > hits[1].score);
+    }
+
+    #[test]
+    fn best_passage_prefers_the_densest_paragraph() {
+        let index = SearchIndex::new([Document {
+            name: "x",
+            description: "",
+            body: "",
+        }]);
+        let body = "Intro about cache.\n\nHow to refresh the cache and prune stale cache entries.\n\nOutro.";
+        assert_eq!(
+            index.best_passage(body, "refresh cache").as_deref(),
+            Some("How to refresh the cache and prune stale cache entries.")
+        );
+        assert_eq!(index.best_passage(body, "zebra"), None);
+    }
+
+    #[test]
+    fn suggests_close_names() {
+        let names = ["brand-guidelines", "pdf", "docx", "canvas-design", "xlsx"];
+        assert_eq!(suggest("brand-guideline", names), vec!["brand-guidelines"]);
+        assert_eq!(suggest("pdf-skill", names), vec!["pdf"]);
+        assert_eq!(suggest("Brand_Guidelines", names), vec!["brand-guidelines"]);
+        assert!(suggest("zebra", names).is_empty());
+        assert_eq!(
+            did_you_mean("docs", names),
+            " Did you mean 'docx'?".to_string()
+        );
+        assert_eq!(did_you_mean("zebra", names), "");
+    }
+}
@@ -1,15 +1,55 @@
 use anyhow::{bail, Context, Result};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
 
-#[derive(Deserialize, Debug, Clone)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
 pub struct SkillMeta {
     pub name: String,
     pub description: String,
+    pub license: Option<String>,
+    /// `allowed-tools` in SKILL.md.
+    pub allowed_tools: Vec<String>,
+    /// Other skills this one expects to be installed alongside it.
+    pub requires: Vec<String>,
+    pub version: Option<String>,
+    pub metadata: BTreeMap<String, serde_yaml::Value>,
+    /// Front-matter keys sk doesn't model, kept verbatim.
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// The optional front-matter fields, serialized under their SKILL.md key names
+/// so JSON consumers can filter on `license` or `allowed-tools` directly.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct FrontMatterFields {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[serde(rename = "allowed-tools", skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, serde_yaml::Value>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub extra: BTreeMap<String, serde_yaml::Value>,
+}
+
+impl SkillMeta {
+    pub fn front_matter(&self) -> FrontMatterFields {
+        FrontMatterFields {
+            license: self.license.clone(),
+            allowed_tools: self.allowed_tools.clone(),
+            requires: self.requires.clone(),
+            version: self.version.clone(),
+            metadata: self.metadata.clone(),
+            extra: self.extra.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -79,14 +119,75 @@ pub fn parse_skill_frontmatter_str(text: &str) -> Result<SkillMeta> {
         .get(1)
         .map(|m| m.as_str())
         .context("empty YAML front-matter")?;
-    match serde_yaml::from_str::<SkillMeta>(yaml) {
-        Ok(meta) => Ok(meta),
+    let mapping = match serde_yaml::from_str::<serde_yaml::Value>(yaml) {
+        Ok(serde_yaml::Value::Mapping(mapping)) => mapping,
+        Ok(_) => bail!("SKILL front-matter is not a YAML mapping"),
         Err(err) => {
             if let Some(meta) = parse_frontmatter_kv_lines(yaml) {
                 return Ok(meta);
             }
             Err(err).context("unable to parse SKILL front-matter as YAML")?
         }
+    };
+    meta_from_mapping(mapping)
+}
+
+/// Build `SkillMeta` from parsed front-matter. `name` and `description` are
+/// required; an optional field of the wrong type is dropped with a warning
+/// instead of failing the whole parse, so one bad `license` doesn't cost the
+/// skill its `requires` or `allowed-tools`.
+fn meta_from_mapping(mut mapping: serde_yaml::Mapping) -> Result<SkillMeta> {
+    let mut required = |key: &str| -> Result<String> {
+        let value = mapping
+            .remove(key)
+            .with_context(|| format!("SKILL front-matter is missing '{key}'"))?;
+        deserialize_scalar_string(value)
+            .with_context(|| format!("invalid '{key}' in SKILL front-matter"))?
+            .with_context(|| format!("SKILL front-matter has an empty '{key}'"))
+    };
+    let name = required("name")?;
+    let description = required("description")?;
+    let license = lenient_field(&mut mapping, &name, "license", deserialize_scalar_string);
+    let allowed_tools = lenient_field(
+        &mut mapping,
+        &name,
+        "allowed-tools",
+        deserialize_string_list,
+    );
+    let requires = lenient_field(&mut mapping, &name, "requires", deserialize_string_list);
+    let version = lenient_field(&mut mapping, &name, "version", deserialize_scalar_string);
+    let metadata = lenient_field(&mut mapping, &name, "metadata", serde_yaml::from_value);
+    let extra = mapping
+        .into_iter()
+        .filter_map(|(key, value)| Some((key.as_str()?.to_string(), value)))
+        .collect();
+    Ok(SkillMeta {
+        name,
+        description,
+        license: license.flatten(),
+        allowed_tools: allowed_tools.unwrap_or_default(),
+        requires: requires.unwrap_or_default(),
+        version: version.flatten(),
+        metadata: metadata.unwrap_or_default(),
+        extra,
+    })
+}
+
+/// Take `key` out of the front-matter and parse it, warning and returning
+/// `None` when it has the wrong shape.
+fn lenient_field<T>(
+    mapping: &mut serde_yaml::Mapping,
+    skill: &str,
+    key: &str,
+    parse: impl FnOnce(serde_yaml::Value) -> Result<T, serde_yaml::Error>,
+) -> Option<T> {
+    let value = mapping.remove(key).filter(|v| !v.is_null())?;
+    match parse(value) {
+        Ok(parsed) => Some(parsed),
+        Err(err) => {
+            eprintln!("Warning: ignoring '{key}' in the front-matter of skill '{skill}': {err}");
+            None
+        }
     }
 }
 
@@ -104,25 +205,123 @@ fn parse_frontmatter_kv_lines(src: &str) -> Option<SkillMeta> {
         let key = key.trim();
         let value = value.trim().trim_matches(['"', '\'']);
         match key {
-            "name" => {
-                if name.is_none() && !value.is_empty() {
-                    name = Some(value.to_string());
-                }
+            "name" if name.is_none() && !value.is_empty() => {
+                name = Some(value.to_string());
             }
-            "description" => {
-                if description.is_none() && !value.is_empty() {
-                    description = Some(value.to_string());
-                }
+            "description" if description.is_none() && !value.is_empty() => {
+                description = Some(value.to_string());
             }
             _ => {}
         }
     }
     match (name, description) {
-        (Some(name), Some(description)) => Some(SkillMeta { name, description }),
+        (Some(name), Some(description)) => Some(SkillMeta {
+            name,
+            description,
+            ..SkillMeta::default()
+        }),
         _ => None,
     }
 }
 
+/// List-valued keys such as `allowed-tools` and `requires` may be a YAML list or
+/// a single string separated by commas or spaces (`Read, Grep` or
+/// `Bash(git add:*) Read`).
+fn deserialize_string_list<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ToolList {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match Option::<ToolList>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(ToolList::One(raw)) => split_tool_list(&raw),
+        Some(ToolList::Many(items)) => items
+            .into_iter()
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    })
+}
+
+fn split_tool_list(raw: &str) -> Vec<String> {
+    let mut tools = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for ch in raw.chars() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                tools.push(std::mem::take(&mut current));
+                continue;
+            }
+            c if c.is_whitespace() && depth == 0 => {
+                tools.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    tools.push(current);
+    tools.retain(|tool| !tool.is_empty());
+    tools
+}
+
+/// YAML reads `version: 1.2` as a number; keep whatever the author wrote as text.
+fn deserialize_scalar_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_yaml::Value::deserialize(deserializer)? {
+        serde_yaml::Value::Null => Ok(None),
+        serde_yaml::Value::String(s) => Ok(Some(s)),
+        serde_yaml::Value::Number(n) => Ok(Some(n.to_string())),
+        serde_yaml::Value::Bool(b) => Ok(Some(b.to_string())),
+        other => Err(serde::de::Error::custom(format!(
+            "expected a scalar, found {other:?}"
+        ))),
+    }
+}
+
+/// The markdown body of a SKILL.md, without its front-matter block.
+pub fn strip_frontmatter(text: &str) -> &str {
+    if !text.starts_with("---") {
+        return text;
+    }
+    let mut offset = match text.find('\n') {
+        Some(idx) => idx + 1,
+        None => return text,
+    };
+    while offset < text.len() {
+        let remainder = &text[offset..];
+        match remainder.find('\n') {
+            Some(rel_end) => {
+                let line = &remainder[..rel_end];
+                if line.trim_end_matches('\r') == "---" {
+                    let mut body = &text[offset + rel_end + 1..];
+                    body = body.strip_prefix('\r').unwrap_or(body);
+                    body = body.strip_prefix('\n').unwrap_or(body);
+                    return body;
+                }
+                offset += rel_end + 1;
+            }
+            None => {
+                if remainder.trim_end_matches('\r') == "---" {
+                    return "";
+                }
+                break;
+            }
+        }
+    }
+    text
+}
+
 pub fn parse_frontmatter_file(path: &Path) -> Result<SkillMeta> {
     let data = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
     parse_skill_frontmatter_str(&data).context("invalid or missing SKILL.md front-matter")
@@ -147,4 +346,44 @@ mod tests {
         assert_eq!(meta.name, "starting-the-task");
         assert_eq!(meta.description, "A short checklist: plan, branch, test.");
     }
+
+    #[test]
+    fn parses_optional_fields_and_keeps_unknown_keys() {
+        let text = "---\nname: demo\ndescription: d\nlicense: MIT\nallowed-tools: Read, Bash(git add:*)\nversion: 2\nmetadata:\n  owner: docs-team\nx-team: platform\n---\n";
+        let meta = parse_skill_frontmatter_str(text).unwrap();
+        assert_eq!(meta.license.as_deref(), Some("MIT"));
+        assert_eq!(meta.allowed_tools, vec!["Read", "Bash(git add:*)"]);
+        assert_eq!(meta.version.as_deref(), Some("2"));
+        assert_eq!(meta.metadata["owner"].as_str(), Some("docs-team"));
+        assert_eq!(meta.extra["x-team"].as_str(), Some("platform"));
+        assert!(!meta.extra.contains_key("license"));
+    }
+
+    #[test]
+    fn allowed_tools_accepts_yaml_lists_and_space_separated_strings() {
+        let list = "---\nname: a\ndescription: d\nallowed-tools:\n  - Read\n  - Grep\n---\n";
+        let meta = parse_skill_frontmatter_str(list).unwrap();
+        assert_eq!(meta.allowed_tools, vec!["Read", "Grep"]);
+
+        let spaced = "---\nname: a\ndescription: d\nallowed-tools: Bash(git:*) Read\n---\n";
+        let meta = parse_skill_frontmatter_str(spaced).unwrap();
+        assert_eq!(meta.allowed_tools, vec!["Bash(git:*)", "Read"]);
+    }
+
+    #[test]
+    fn mistyped_optional_fields_are_dropped_without_losing_the_rest() {
+        let text = "---\nname: demo\ndescription: d\nlicense: [MIT]\nversion: 1.2\nmetadata: x\nrequires: [base-skill]\nallowed-tools: Read\narguments:\n  - topic\n---\n";
+        let meta = parse_skill_frontmatter_str(text).unwrap();
+        assert_eq!(meta.license, None);
+        assert!(meta.metadata.is_empty());
+        assert_eq!(meta.requires, vec!["base-skill"]);
+        assert_eq!(meta.allowed_tools, vec!["Read"]);
+        assert_eq!(meta.version.as_deref(), Some("1.2"));
+        assert!(meta.extra.contains_key("arguments"));
+
+        let numeric = "---\nname: demo\ndescription: d\nlicense: 2024\nrequires: base-skill\n---\n";
+        let meta = parse_skill_frontmatter_str(numeric).unwrap();
+        assert_eq!(meta.license.as_deref(), Some("2024"));
+        assert_eq!(meta.requires, vec!["base-skill"]);
+    }
 }
@@ -0,0 +1,184 @@
+use super::{AutoMergeOutcome, Forge, MergeStatus, PrInfo};
+use crate::git;
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use serde_json::json;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// Gitea and Forgejo (including Codeberg), driven through the REST API since
+/// there is no CLI we can count on being installed.
+pub(super) struct Gitea {
+    api: String,
+    token: Option<String>,
+}
+
+impl Gitea {
+    pub(super) fn new(spec: &git::RepoSpec) -> Self {
+        let base = env::var("SK_GITEA_API_URL")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| format!("https://{}/api/v1", spec.host));
+        Gitea {
+            api: format!(
+                "{}/repos/{}/{}",
+                base.trim_end_matches('/'),
+                spec.owner,
+                spec.repo
+            ),
+            token: ["SK_GITEA_TOKEN", "GITEA_TOKEN"]
+                .iter()
+                .find_map(|key| env::var(key).ok().filter(|v| !v.trim().is_empty())),
+        }
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let req = ureq::request(method, &format!("{}{path}", self.api));
+        match &self.token {
+            Some(token) => req.set("Authorization", &format!("token {token}")),
+            None => req,
+        }
+    }
+
+    fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let resp = self
+            .request("GET", path)
+            .call()
+            .map_err(describe_error)
+            .with_context(|| format!("GET {}{path}", self.api))?;
+        resp.into_json()
+            .with_context(|| format!("parse Gitea response for {path}"))
+    }
+}
+
+impl Forge for Gitea {
+    fn name(&self) -> &'static str {
+        "Gitea"
+    }
+
+    fn unavailable(&self) -> Option<String> {
+        self.token.is_none().then(|| {
+            "Warning: skipping PR automation because no Gitea token is set. Push completed; open a PR manually or export SK_GITEA_TOKEN (or GITEA_TOKEN).".to_string()
+        })
+    }
+
+    fn find_pr(&self, _wt_path: &Path, branch: &str) -> Result<Option<PrInfo>> {
+        // The pulls list cannot be filtered by head branch, so page through it.
+        // Stop only on an empty page: servers may cap `limit` below what we ask.
+        for page in 1.. {
+            let pulls: Vec<GiteaPull> = self.get(&format!(
+                "/pulls?state=all&sort=recentupdate&limit=50&page={page}"
+            ))?;
+            if pulls.is_empty() {
+                break;
+            }
+            if let Some(pr) = pulls
+                .into_iter()
+                .find(|pr| pr.head.as_ref().is_some_and(|h| h.git_ref == branch))
+            {
+                return Ok(Some(pr.into_info()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn create_pr(&self, wt_path: &Path, branch: &str) -> Result<()> {
+        let repo: GiteaRepo = self.get("")?;
+        let (title, body) = head_commit_message(wt_path)?;
+        self.request("POST", "/pulls")
+            .send_json(json!({
+                "head": branch,
+                "base": repo.default_branch,
+                "title": title,
+                "body": body,
+            }))
+            .map_err(describe_error)
+            .context("create Gitea pull request")?;
+        Ok(())
+    }
+
+    fn arm_auto_merge(&self, _wt_path: &Path, pr: &PrInfo) -> AutoMergeOutcome {
+        match self
+            .request("POST", &format!("/pulls/{}/merge", pr.number))
+            .send_json(json!({
+                "Do": "merge",
+                "merge_when_checks_succeed": true,
+            })) {
+            Ok(_) => AutoMergeOutcome::Armed,
+            Err(err) => AutoMergeOutcome::Skipped(format!("{:#}", describe_error(err))),
+        }
+    }
+
+    fn merge_status(&self, pr: &PrInfo) -> Result<MergeStatus> {
+        let pull: GiteaPull = self.get(&format!("/pulls/{}", pr.number))?;
+        Ok(MergeStatus {
+            merged: pull.merged,
+            closed: !pull.merged && pull.state.eq_ignore_ascii_case("closed"),
+            merge_commit: pull.merge_commit_sha.filter(|sha| !sha.is_empty()),
+        })
+    }
+}
+
+/// Use the pushed commit's subject and body, mirroring `gh pr create --fill`.
+fn head_commit_message(wt_path: &Path) -> Result<(String, String)> {
+    let out = Command::new("git")
+        .current_dir(wt_path)
+        .args(["log", "-1", "--format=%s%n%b"])
+        .output()
+        .context("read head commit message")?;
+    let text = String::from_utf8_lossy(&out.stdout);
+    let (title, body) = text.split_once('\n').unwrap_or((&text, ""));
+    Ok((title.trim().to_string(), body.trim().to_string()))
+}
+
+fn describe_error(err: ureq::Error) -> anyhow::Error {
+    match err {
+        ureq::Error::Status(code, resp) => {
+            let body = resp.into_string().unwrap_or_default();
+            let body = body.trim();
+            if body.is_empty() {
+                anyhow!("Gitea API returned HTTP {code}")
+            } else {
+                anyhow!("Gitea API returned HTTP {code}: {body}")
+            }
+        }
+        other => anyhow!(other),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaRepo {
+    default_branch: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaBranchRef {
+    #[serde(rename = "ref")]
+    git_ref: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaPull {
+    number: u64,
+    #[serde(default)]
+    html_url: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    mergeable: Option<bool>,
+    merge_commit_sha: Option<String>,
+    head: Option<GiteaBranchRef>,
+}
+
+impl GiteaPull {
+    fn into_info(self) -> PrInfo {
+        let open = self.state.eq_ignore_ascii_case("open");
+        PrInfo {
+            number: self.number,
+            url: self.html_url,
+            conflicted: open && self.mergeable == Some(false),
+        }
+    }
+}
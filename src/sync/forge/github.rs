@@ -0,0 +1,223 @@
+use super::{format_cli_failure, AutoMergeOutcome, Forge, MergeStatus, PrInfo};
+use crate::git;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// GitHub and GitHub Enterprise, driven through the `gh` CLI.
+pub(super) struct GitHub {
+    spec: git::RepoSpec,
+    selector: String,
+}
+
+impl GitHub {
+    pub(super) fn new(spec: &git::RepoSpec) -> Self {
+        let selector = if spec.host.is_empty() {
+            format!("{}/{}", spec.owner, spec.repo)
+        } else {
+            format!("{}/{}/{}", spec.host, spec.owner, spec.repo)
+        };
+        GitHub {
+            spec: spec.clone(),
+            selector,
+        }
+    }
+
+    fn gh(&self, wt_path: Option<&Path>, args: &[&str]) -> Result<Vec<u8>> {
+        let mut cmd = Command::new("gh");
+        if let Some(dir) = wt_path {
+            cmd.current_dir(dir);
+        }
+        let label = format!("gh {}", args[..2].join(" "));
+        let out = cmd
+            .args(args)
+            .args(["-R", &self.selector])
+            .output()
+            .with_context(|| format!("run {label}"))?;
+        if !out.status.success() {
+            bail!(
+                "{label} failed: {}",
+                format_cli_failure("gh", &out.stdout, &out.stderr)
+            );
+        }
+        Ok(out.stdout)
+    }
+}
+
+impl Forge for GitHub {
+    fn name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn unavailable(&self) -> Option<String> {
+        let gh_missing =
+            env::var_os("SK_FORCE_GH_MISSING").is_some() || which::which("gh").is_err();
+        gh_missing.then(|| {
+            "Warning: skipping PR automation because the GitHub CLI ('gh') is unavailable. Push completed; open a PR manually or install gh from https://cli.github.com/.".to_string()
+        })
+    }
+
+    fn find_pr(&self, wt_path: &Path, branch: &str) -> Result<Option<PrInfo>> {
+        let stdout = self.gh(
+            Some(wt_path),
+            &[
+                "pr",
+                "list",
+                "--state",
+                "all",
+                "--head",
+                branch,
+                "--limit",
+                "1",
+                "--json",
+                "number,url,mergeStateStatus,mergeable",
+            ],
+        )?;
+        let mut entries: Vec<GhPrInfo> =
+            serde_json::from_slice(&stdout).context("parse gh pr list JSON output")?;
+        Ok(entries.pop().map(|pr| PrInfo {
+            conflicted: pr.is_conflicted(),
+            number: pr.number,
+            url: pr.url,
+        }))
+    }
+
+    fn create_pr(&self, wt_path: &Path, branch: &str) -> Result<()> {
+        self.gh(Some(wt_path), &["pr", "create", "--fill", "--head", branch])?;
+        Ok(())
+    }
+
+    fn arm_auto_merge(&self, wt_path: &Path, pr: &PrInfo) -> AutoMergeOutcome {
+        let number = pr.number.to_string();
+        match Command::new("gh")
+            .current_dir(wt_path)
+            .args([
+                "pr",
+                "merge",
+                &number,
+                "--auto",
+                "--merge",
+                "-R",
+                &self.selector,
+            ])
+            .output()
+        {
+            Ok(out) if out.status.success() => AutoMergeOutcome::Armed,
+            Ok(out) => {
+                AutoMergeOutcome::Skipped(format_cli_failure("gh", &out.stdout, &out.stderr))
+            }
+            Err(err) => AutoMergeOutcome::Skipped(err.to_string()),
+        }
+    }
+
+    fn merge_status(&self, pr: &PrInfo) -> Result<MergeStatus> {
+        let stdout = self.gh(
+            None,
+            &[
+                "pr",
+                "view",
+                &pr.number.to_string(),
+                "--json",
+                "state,mergeCommit",
+            ],
+        )?;
+        let status: GhPrMergeStatus =
+            serde_json::from_slice(&stdout).context("parse gh pr view JSON output")?;
+        Ok(MergeStatus {
+            merged: status.has_state("merged"),
+            closed: status.has_state("closed"),
+            merge_commit: status.merge_commit_oid(),
+        })
+    }
+
+    fn auto_merge_tip(&self, reason: &str) -> Option<String> {
+        if !reason
+            .to_ascii_lowercase()
+            .contains("enablepullrequestautomerge")
+        {
+            return None;
+        }
+
+        let spec = &self.spec;
+        let repo_slug = format!("{}/{}", spec.owner, spec.repo);
+        let cmd = if spec.host.is_empty() || spec.host.eq_ignore_ascii_case("github.com") {
+            format!("gh repo edit {repo_slug} --enable-auto-merge")
+        } else {
+            format!(
+                "gh repo edit -R {}/{repo_slug} --enable-auto-merge",
+                spec.host
+            )
+        };
+        let host = if spec.host.is_empty() {
+            "github.com"
+        } else {
+            spec.host.as_str()
+        };
+        let settings_url = format!("https://{host}/{repo_slug}/settings");
+        Some(format!(
+            "Tip: enable auto-merge with `{cmd}` or toggle Auto-merge under Settings → General ({settings_url})."
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GhPrInfo {
+    number: u64,
+    url: String,
+    #[serde(rename = "mergeStateStatus")]
+    merge_state_status: Option<String>,
+    mergeable: Option<String>,
+}
+
+impl GhPrInfo {
+    fn is_conflicted(&self) -> bool {
+        matches!(
+            self.merge_state_status
+                .as_deref()
+                .map(|s| s.eq_ignore_ascii_case("dirty")),
+            Some(true)
+        ) || matches!(
+            self.mergeable
+                .as_deref()
+                .map(|s| s.eq_ignore_ascii_case("conflicting")),
+            Some(true)
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GhPrMergeStatus {
+    state: Option<String>,
+    #[serde(rename = "mergeCommit")]
+    merge_commit: Option<JsonValue>,
+}
+
+impl GhPrMergeStatus {
+    fn has_state(&self, wanted: &str) -> bool {
+        matches!(
+            self.state
+                .as_deref()
+                .map(|s| s.eq_ignore_ascii_case(wanted)),
+            Some(true)
+        )
+    }
+
+    fn merge_commit_oid(&self) -> Option<String> {
+        self.merge_commit.as_ref().and_then(|value| match value {
+            JsonValue::String(s) if !s.is_empty() => Some(s.clone()),
+            JsonValue::Object(map) => map
+                .get("oid")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .or_else(|| {
+                    map.get("sha")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string())
+                }),
+            _ => None,
+        })
+    }
+}
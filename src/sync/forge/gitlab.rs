@@ -0,0 +1,130 @@
+use super::{format_cli_failure, AutoMergeOutcome, Forge, MergeStatus, PrInfo};
+use crate::git;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+use std::process::Command;
+
+/// GitLab (SaaS or self-managed) merge requests, driven through the `glab` CLI.
+pub(super) struct GitLab {
+    selector: String,
+}
+
+impl GitLab {
+    pub(super) fn new(spec: &git::RepoSpec) -> Self {
+        // glab accepts a full project URL, which also pins the host.
+        GitLab {
+            selector: format!("https://{}/{}/{}", spec.host, spec.owner, spec.repo),
+        }
+    }
+
+    fn glab(&self, wt_path: Option<&Path>, args: &[&str]) -> Result<Vec<u8>> {
+        let mut cmd = Command::new("glab");
+        if let Some(dir) = wt_path {
+            cmd.current_dir(dir);
+        }
+        let label = format!("glab {}", args[..2].join(" "));
+        let out = cmd
+            .args(args)
+            .args(["-R", &self.selector])
+            .output()
+            .with_context(|| format!("run {label}"))?;
+        if !out.status.success() {
+            bail!(
+                "{label} failed: {}",
+                format_cli_failure("glab", &out.stdout, &out.stderr)
+            );
+        }
+        Ok(out.stdout)
+    }
+}
+
+impl Forge for GitLab {
+    fn name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    fn unavailable(&self) -> Option<String> {
+        which::which("glab").is_err().then(|| {
+            "Warning: skipping MR automation because the GitLab CLI ('glab') is unavailable. Push completed; open a merge request manually or install glab from https://gitlab.com/gitlab-org/cli.".to_string()
+        })
+    }
+
+    fn find_pr(&self, wt_path: &Path, branch: &str) -> Result<Option<PrInfo>> {
+        let stdout = self.glab(
+            Some(wt_path),
+            &[
+                "mr",
+                "list",
+                "--source-branch",
+                branch,
+                "--all",
+                "--per-page",
+                "1",
+                "-F",
+                "json",
+            ],
+        )?;
+        let entries: Vec<GlabMr> =
+            serde_json::from_slice(&stdout).context("parse glab mr list JSON output")?;
+        Ok(entries.into_iter().next().map(|mr| PrInfo {
+            number: mr.iid,
+            url: mr.web_url,
+            conflicted: mr.has_conflicts,
+        }))
+    }
+
+    fn create_pr(&self, wt_path: &Path, branch: &str) -> Result<()> {
+        self.glab(
+            Some(wt_path),
+            &["mr", "create", "--fill", "--yes", "--source-branch", branch],
+        )?;
+        Ok(())
+    }
+
+    fn arm_auto_merge(&self, wt_path: &Path, pr: &PrInfo) -> AutoMergeOutcome {
+        let iid = pr.number.to_string();
+        match Command::new("glab")
+            .current_dir(wt_path)
+            .args([
+                "mr",
+                "merge",
+                &iid,
+                "--auto-merge",
+                "--yes",
+                "-R",
+                &self.selector,
+            ])
+            .output()
+        {
+            Ok(out) if out.status.success() => AutoMergeOutcome::Armed,
+            Ok(out) => {
+                AutoMergeOutcome::Skipped(format_cli_failure("glab", &out.stdout, &out.stderr))
+            }
+            Err(err) => AutoMergeOutcome::Skipped(err.to_string()),
+        }
+    }
+
+    fn merge_status(&self, pr: &PrInfo) -> Result<MergeStatus> {
+        let stdout = self.glab(None, &["mr", "view", &pr.number.to_string(), "-F", "json"])?;
+        let mr: GlabMr =
+            serde_json::from_slice(&stdout).context("parse glab mr view JSON output")?;
+        Ok(MergeStatus {
+            merged: mr.state.eq_ignore_ascii_case("merged"),
+            closed: mr.state.eq_ignore_ascii_case("closed"),
+            merge_commit: mr.merge_commit_sha.filter(|sha| !sha.is_empty()),
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GlabMr {
+    iid: u64,
+    #[serde(default)]
+    web_url: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    has_conflicts: bool,
+    merge_commit_sha: Option<String>,
+}
@@ -0,0 +1,175 @@
+//! Code hosts that sync-back can open and auto-merge pull requests on.
+
+mod gitea;
+mod github;
+mod gitlab;
+
+use crate::git;
+use anyhow::{bail, Result};
+use std::path::Path;
+
+/// A pull (or merge) request as far as sync-back cares about it.
+#[derive(Debug, Clone)]
+pub(crate) struct PrInfo {
+    pub(crate) number: u64,
+    pub(crate) url: String,
+    pub(crate) conflicted: bool,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MergeStatus {
+    pub(crate) merged: bool,
+    pub(crate) closed: bool,
+    pub(crate) merge_commit: Option<String>,
+}
+
+pub(crate) enum AutoMergeOutcome {
+    Armed,
+    Conflict,
+    Skipped(String),
+}
+
+pub(crate) trait Forge {
+    /// Product name for messages, e.g. "GitHub".
+    fn name(&self) -> &'static str;
+    /// A full warning when automation can't run here (missing CLI or token).
+    fn unavailable(&self) -> Option<String>;
+    /// The most recent PR whose head is `branch`, in any state.
+    fn find_pr(&self, wt_path: &Path, branch: &str) -> Result<Option<PrInfo>>;
+    /// Open a PR from `branch` into the default branch, titled from its head commit.
+    fn create_pr(&self, wt_path: &Path, branch: &str) -> Result<()>;
+    fn arm_auto_merge(&self, wt_path: &Path, pr: &PrInfo) -> AutoMergeOutcome;
+    fn merge_status(&self, pr: &PrInfo) -> Result<MergeStatus>;
+    /// Extra guidance when arming auto-merge failed with `reason`.
+    fn auto_merge_tip(&self, _reason: &str) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+    Gitea,
+}
+
+impl ForgeKind {
+    fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "github" => Ok(ForgeKind::GitHub),
+            "gitlab" => Ok(ForgeKind::GitLab),
+            "gitea" | "forgejo" => Ok(ForgeKind::Gitea),
+            other => bail!("unknown forge '{other}' (expected github, gitlab, or gitea)"),
+        }
+    }
+
+    /// Best guess from the host name; anything unrecognised is treated as
+    /// GitHub (Enterprise), which is what sync-back always assumed.
+    fn detect(host: &str) -> Self {
+        let host = host.to_ascii_lowercase();
+        if host.contains("gitlab") {
+            ForgeKind::GitLab
+        } else if ["gitea", "forgejo", "codeberg"]
+            .iter()
+            .any(|hint| host.contains(hint))
+        {
+            ForgeKind::Gitea
+        } else {
+            ForgeKind::GitHub
+        }
+    }
+}
+
+/// The `forge` config value: `auto`, one kind for every host, or
+/// comma-separated `host=kind` pairs (optionally with a bare fallback kind).
+#[derive(Debug, Default)]
+pub struct ForgeSetting {
+    fallback: Option<ForgeKind>,
+    by_host: Vec<(String, ForgeKind)>,
+}
+
+impl ForgeSetting {
+    pub fn parse(raw: &str) -> Result<Self> {
+        let mut setting = ForgeSetting::default();
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("auto") {
+            return Ok(setting);
+        }
+        for part in trimmed.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            match part.split_once('=') {
+                Some((host, kind)) => {
+                    let host = host.trim();
+                    if host.is_empty() {
+                        bail!("forge entry '{part}' is missing a host");
+                    }
+                    setting
+                        .by_host
+                        .push((host.to_ascii_lowercase(), ForgeKind::parse(kind)?));
+                }
+                None if setting.fallback.is_some() => {
+                    bail!("forge may name at most one kind without a host, got '{raw}'")
+                }
+                None => setting.fallback = Some(ForgeKind::parse(part)?),
+            }
+        }
+        Ok(setting)
+    }
+
+    pub fn kind_for(&self, host: &str) -> ForgeKind {
+        self.by_host
+            .iter()
+            .find(|(h, _)| h.eq_ignore_ascii_case(host))
+            .map(|(_, kind)| *kind)
+            .or(self.fallback)
+            .unwrap_or_else(|| ForgeKind::detect(host))
+    }
+}
+
+pub(crate) fn select(spec: &git::RepoSpec, setting: &str) -> Result<Box<dyn Forge>> {
+    let kind = ForgeSetting::parse(setting)?.kind_for(&spec.host);
+    Ok(match kind {
+        ForgeKind::GitHub => Box::new(github::GitHub::new(spec)),
+        ForgeKind::GitLab => Box::new(gitlab::GitLab::new(spec)),
+        ForgeKind::Gitea => Box::new(gitea::Gitea::new(spec)),
+    })
+}
+
+/// Combine a failed command's stderr and stdout into one message.
+fn format_cli_failure(tool: &str, stdout: &[u8], stderr: &[u8]) -> String {
+    let mut combined = String::new();
+    if !stderr.is_empty() {
+        combined.push_str(&String::from_utf8_lossy(stderr));
+    }
+    if !stdout.is_empty() {
+        combined.push_str(&String::from_utf8_lossy(stdout));
+    }
+    let trimmed = combined.trim();
+    if trimmed.is_empty() {
+        format!("{tool} command failed")
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_forge_from_host() {
+        let auto = ForgeSetting::parse("auto").unwrap();
+        assert_eq!(auto.kind_for("github.com"), ForgeKind::GitHub);
+        assert_eq!(auto.kind_for("gitlab.example.com"), ForgeKind::GitLab);
+        assert_eq!(auto.kind_for("codeberg.org"), ForgeKind::Gitea);
+        assert_eq!(auto.kind_for("git.corp.internal"), ForgeKind::GitHub);
+    }
+
+    #[test]
+    fn explicit_hosts_win_over_fallback_and_detection() {
+        let setting = ForgeSetting::parse("git.corp.internal=gitea, gitlab").unwrap();
+        assert_eq!(setting.kind_for("git.corp.internal"), ForgeKind::Gitea);
+        assert_eq!(setting.kind_for("github.com"), ForgeKind::GitLab);
+        assert!(ForgeSetting::parse("bitbucket").is_err());
+        assert!(ForgeSetting::parse("github, gitlab").is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use super::target::SyncTarget;
-use crate::install;
+use crate::{digest, install};
 #[cfg(not(any(unix, windows)))]
 use anyhow::bail;
 use anyhow::{Context, Result};
@@ -9,7 +9,6 @@ use std::os::unix::fs::symlink;
 #[cfg(windows)]
 use std::os::windows::fs::{symlink_dir, symlink_file};
 use std::path::Path;
-use walkdir::WalkDir;
 
 pub(crate) fn purge_children_except_git(dir: &Path) -> Result<()> {
     if !dir.is_dir() {
@@ -40,8 +39,9 @@ pub(crate) fn refresh_install_from_commit(
     install::extract_subdir_from_commit(&target.cache_dir, commit, &target.skill_path, dest)
 }
 
+/// Copy `src` into `dest`, leaving out anything matched by a `.skignore`.
 pub(crate) fn mirror_dir(src: &Path, dest: &Path) -> Result<()> {
-    for entry in WalkDir::new(src).follow_links(false) {
+    for entry in digest::walk_skill_dir(src) {
         let entry = entry?;
         let rel = entry
             .path()
@@ -51,10 +51,13 @@ pub(crate) fn mirror_dir(src: &Path, dest: &Path) -> Result<()> {
             continue;
         }
         let target = dest.join(rel);
-        if entry.file_type().is_dir() {
+        let Some(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
             fs::create_dir_all(&target)
                 .with_context(|| format!("create dir {}", target.display()))?;
-        } else if entry.file_type().is_symlink() {
+        } else if file_type.is_symlink() {
             copy_symlink(entry.path(), &target)?;
         } else {
             if let Some(parent) = target.parent() {
@@ -75,7 +78,7 @@ fn remove_existing(path: &Path) {
 }
 
 #[cfg(unix)]
-fn copy_symlink(src: &Path, dest: &Path) -> Result<()> {
+pub(crate) fn copy_symlink(src: &Path, dest: &Path) -> Result<()> {
     let target = fs::read_link(src).with_context(|| format!("read symlink {}", src.display()))?;
     if let Some(parent) = dest.parent() {
         fs::create_dir_all(parent)
@@ -86,7 +89,7 @@ fn copy_symlink(src: &Path, dest: &Path) -> Result<()> {
 }
 
 #[cfg(windows)]
-fn copy_symlink(src: &Path, dest: &Path) -> Result<()> {
+pub(crate) fn copy_symlink(src: &Path, dest: &Path) -> Result<()> {
     let target = fs::read_link(src).with_context(|| format!("read symlink {}", src.display()))?;
     if let Some(parent) = dest.parent() {
         fs::create_dir_all(parent)
@@ -105,7 +108,7 @@ fn copy_symlink(src: &Path, dest: &Path) -> Result<()> {
 }
 
 #[cfg(not(any(unix, windows)))]
-fn copy_symlink(src: &Path, _dest: &Path) -> Result<()> {
+pub(crate) fn copy_symlink(src: &Path, _dest: &Path) -> Result<()> {
     bail!(
         "symlinks at {} are not supported on this platform",
         src.display()
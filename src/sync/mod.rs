@@ -1,206 +1,318 @@
-mod fs_utils;
+pub(crate) mod forge;
+pub(crate) mod fs_utils;
+mod patch;
 mod pr;
 mod target;
 
 use crate::{config, digest, git, lock, paths};
 use anyhow::{bail, Context, Result};
 use chrono::Utc;
+use std::collections::HashSet;
 use std::env;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use tempfile::TempDir;
+use tempfile::{NamedTempFile, TempDir};
 
 use fs_utils::{mirror_dir, purge_children_except_git, refresh_install_from_commit};
+pub use patch::{run_apply_patch, ApplyPatchArgs};
 use pr::{automate_pr_flow, maybe_wait_for_auto_merge, PrAutomationReport};
 use target::{build_existing_target, build_new_target, SyncTarget};
 
 pub struct SyncBackArgs<'a> {
-    pub installed_name: &'a str,
+    pub installed_names: Vec<&'a str>,
+    pub all_modified: bool,
     pub branch: Option<&'a str>,
     pub message: Option<&'a str>,
     pub root: Option<&'a str>,
     pub repo: Option<&'a str>,
     pub skill_path: Option<&'a str>,
     pub https: bool,
+    pub dry_run: bool,
+    pub rebase: bool,
+    pub patch: Option<&'a str>,
+    pub patch_format: Option<&'a str>,
 }
 
-struct SyncSession<'a> {
-    args: SyncBackArgs<'a>,
+/// One installed skill and where it syncs back to.
+struct SyncItem {
+    name: String,
     dest_installed: PathBuf,
+    target: SyncTarget,
+}
+
+/// Publishes every requested skill that shares one upstream repo: a commit per
+/// skill on a single branch, one PR, and one lockfile update.
+struct SyncSession<'a> {
+    args: &'a SyncBackArgs<'a>,
+    items: Vec<SyncItem>,
+    base_commit: String,
     lock_path: PathBuf,
     lockfile: lock::Lockfile,
-    target: SyncTarget,
     branch_name: String,
+    forge: String,
     worktree_base: Option<TempDir>,
 }
 
 impl<'a> SyncSession<'a> {
-    fn new(args: SyncBackArgs<'a>) -> Result<Self> {
-        let project_root = git::ensure_git_repo()?;
-        let cfg = config::load_or_default()?;
-        let install_root_rel = args.root.unwrap_or(&cfg.default_root);
-        let install_root = paths::resolve_project_path(&project_root, install_root_rel);
-        let dest_installed = install_root.join(args.installed_name);
-        if !dest_installed.exists() {
-            bail!(
-                "installed dir missing for '{}'. Run 'sk doctor --apply' to rebuild first.",
-                args.installed_name
-            );
-        }
-        let lock_path = project_root.join("skills.lock.json");
+    fn new(
+        args: &'a SyncBackArgs<'a>,
+        items: Vec<SyncItem>,
+        lock_path: PathBuf,
+        forge: String,
+    ) -> Result<Self> {
+        let base_commit = pick_base_commit(&items)?;
         let lockfile = lock::Lockfile::load_or_empty(&lock_path)?;
-        let lock_index = lockfile
-            .skills
-            .iter()
-            .position(|s| s.install_name == args.installed_name);
-        let target = if let Some(idx) = lock_index {
-            build_existing_target(lockfile.skills[idx].clone(), idx)?
-        } else {
-            let repo_value = match args.repo {
-                Some(raw) if !raw.trim().is_empty() => raw.trim().to_string(),
-                _ => {
-                    let trimmed = cfg.default_repo.trim();
-                    if trimmed.is_empty() {
-                        bail!(
-                            "default_repo is not configured. Run 'sk config set default_repo <repo>' or pass --repo <target> when calling 'sk sync-back {}'.",
-                            args.installed_name
-                        );
-                    }
-                    trimmed.to_string()
-                }
-            };
-            build_new_target(
-                &repo_value,
-                args.skill_path,
-                args.installed_name,
-                args.https,
-                &cfg,
-            )?
-        };
-        let branch_name = args
-            .branch
-            .map(|b| b.to_string())
-            .unwrap_or_else(|| default_branch_name(args.installed_name));
+        let branch_name = args.branch.map(|b| b.to_string()).unwrap_or_else(|| {
+            if items.len() == 1 {
+                default_branch_name(&items[0].name)
+            } else {
+                default_branch_name("batch")
+            }
+        });
         Ok(Self {
             args,
-            dest_installed,
+            items,
+            base_commit,
             lock_path,
             lockfile,
-            target,
             branch_name,
+            forge,
             worktree_base: None,
         })
     }
 
+    /// The first item's target; every item in a session shares its repo and cache.
+    fn repo(&self) -> &SyncTarget {
+        &self.items[0].target
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.items.iter().map(|item| item.name.clone()).collect()
+    }
+
+    /// Tracked skills whose path changed upstream between their locked commit
+    /// and the tip of the default branch (the branch the PR targets).
+    fn diverged(&self) -> Result<(git::TrackedTip, Vec<String>)> {
+        let repo = self.repo();
+        let tip = git::resolve_tracked_tip(&repo.cache_dir, &repo.spec, &git::Tracking::default())?;
+        let mut lines = Vec::new();
+        for item in &self.items {
+            let target = &item.target;
+            if target.lock_index.is_none() || target.commit == tip.commit {
+                continue;
+            }
+            if git::diff_includes_path(
+                &target.cache_dir,
+                &target.commit,
+                &tip.commit,
+                &target.skill_path,
+            )? {
+                lines.push(format!(
+                    "'{}' changed upstream between its locked commit {} and {} ({})",
+                    item.name,
+                    short_sha(&target.commit),
+                    tip.label,
+                    short_sha(&tip.commit)
+                ));
+            }
+        }
+        Ok((tip, lines))
+    }
+
     fn execute(mut self) -> Result<()> {
-        let (wt_path, mut guard) = self.add_worktree()?;
-        self.sync_installed_dir(&wt_path)?;
-        let head = match self.commit_worktree(&wt_path, &mut guard)? {
-            Some(head) => head,
-            None => return Ok(()),
+        if let Some(out) = self.args.patch {
+            return self.export_patch(Path::new(out));
+        }
+        let (tip, diverged) = self.diverged()?;
+        if self.args.dry_run {
+            for line in &diverged {
+                eprintln!("Warning: {line}; publishing needs --rebase.");
+            }
+            return self.preview();
+        }
+        if !diverged.is_empty() && !self.args.rebase {
+            bail!(
+                "{}.\nThe PR would conflict with or undo those upstream changes. Re-run with --rebase to replay your edits onto {}, or run 'sk upgrade <name> --merge' first.",
+                diverged.join(";\n"),
+                tip.label
+            );
+        }
+        let (wt_path, mut guard) = self.add_worktree(false)?;
+        let mut committed = Vec::new();
+        let mut head = None;
+        for idx in 0..self.items.len() {
+            sync_installed_dir(&self.items[idx], &wt_path)?;
+            if let Some(commit) = self.commit_worktree(&self.items[idx], &wt_path)? {
+                committed.push(idx);
+                head = Some(commit);
+            }
+        }
+        let Some(mut head) = head else {
+            self.cleanup_noop_branch(&wt_path, &mut guard);
+            return Ok(());
         };
+        let rebased = !diverged.is_empty();
+        if rebased {
+            match self.rebase_onto(&wt_path, &tip) {
+                Ok(new_head) => head = new_head,
+                Err(err) => {
+                    self.cleanup_noop_branch(&wt_path, &mut guard);
+                    return Err(err);
+                }
+            }
+        }
         let pr_report = self.push_branch_and_maybe_open_pr(&wt_path)?;
         self.remove_worktree(&mut guard, &wt_path);
 
         let final_commit = self.finalize_commit(&head, pr_report.as_ref())?;
-        if final_commit != head {
+        let mut updates = Vec::new();
+        for idx in committed {
+            let item = &self.items[idx];
+            if final_commit != head {
+                println!(
+                    "Auto-merge landed additional upstream changes; refreshing '{}' to {}.",
+                    item.name,
+                    short_sha(&final_commit)
+                );
+                refresh_install_from_commit(&item.target, &item.dest_installed, &final_commit)?;
+            } else if rebased {
+                println!(
+                    "Refreshing '{}' to {} so it includes the upstream changes it was rebased onto.",
+                    item.name,
+                    short_sha(&final_commit)
+                );
+                refresh_install_from_commit(&item.target, &item.dest_installed, &final_commit)?;
+            }
+            let digest =
+                digest::digest_dir_versioned(&item.dest_installed, self.lockfile.digest_version)?;
+            let files = digest::manifest_dir(&item.dest_installed)?;
+            updates.push((idx, digest, files));
+        }
+        self.write_lock_entries(&final_commit, updates)?;
+        Ok(())
+    }
+
+    /// Replay the sync-back commits onto the upstream tip. On conflict the
+    /// rebase is aborted and the conflicting paths are reported; nothing is pushed.
+    fn rebase_onto(&self, wt_path: &Path, tip: &git::TrackedTip) -> Result<String> {
+        let wt = wt_path.to_string_lossy().to_string();
+        let out = Command::new("git")
+            .args(["-C", &wt, "rebase", &tip.commit])
+            .output()
+            .context("spawn git rebase")?;
+        if !out.status.success() {
+            let conflicts = git_stdout(
+                &["-C", &wt, "diff", "--name-only", "--diff-filter=U"],
+                "git diff --diff-filter=U",
+            )
+            .unwrap_or_default();
+            let _ = Command::new("git")
+                .args(["-C", &wt, "rebase", "--abort"])
+                .status();
+            let files: Vec<&str> = conflicts.lines().filter(|l| !l.trim().is_empty()).collect();
+            if files.is_empty() {
+                bail!(
+                    "git rebase onto {} failed: {}",
+                    tip.label,
+                    String::from_utf8_lossy(&out.stderr).trim()
+                );
+            }
+            bail!(
+                "rebasing onto {} ({}) conflicts in:\n  {}\nNothing was pushed. Run 'sk upgrade <name> --merge' to resolve locally, then sync back again.",
+                tip.label,
+                short_sha(&tip.commit),
+                files.join("\n  ")
+            );
+        }
+        let head = git::rev_parse(wt_path, "HEAD")?;
+        println!(
+            "Rebased local edits onto {} ({}).",
+            tip.label,
+            short_sha(&tip.commit)
+        );
+        Ok(head)
+    }
+
+    /// Mirror the installs into a throwaway worktree and print what sync-back
+    /// would commit, without creating a branch, committing, or pushing.
+    fn preview(mut self) -> Result<()> {
+        let (wt_path, mut guard) = self.add_worktree(true)?;
+        for item in &self.items {
+            sync_installed_dir(item, &wt_path)?;
+        }
+        let wt = wt_path.to_string_lossy().to_string();
+        run(
+            Command::new("git").args(["-C", &wt, "add", "-A"]),
+            "git add",
+        )?;
+        let stat = git_stdout(
+            &["-C", &wt, "diff", "--cached", "--stat"],
+            "git diff --stat",
+        )?;
+        let spec = &self.repo().spec;
+        if stat.trim().is_empty() {
             println!(
-                "Auto-merge landed additional upstream changes; refreshing '{}' to {}.",
-                self.args.installed_name,
-                short_sha(&final_commit)
+                "No changes to sync back for {}; {}/{} at {} already matches.",
+                quoted_names(&self.names()),
+                spec.owner,
+                spec.repo,
+                short_sha(&self.base_commit)
             );
-            self.refresh_install(&final_commit)?;
+        } else {
+            let patch = git_stdout(&["-C", &wt, "diff", "--cached", "--no-color"], "git diff")?;
+            let described = self
+                .items
+                .iter()
+                .map(|item| format!("'{}' ({})", item.name, item.target.skill_path))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!(
+                "Dry run: sync-back of {described} would publish to {}/{} on top of {}:",
+                spec.owner,
+                spec.repo,
+                short_sha(&self.base_commit)
+            );
+            print!("{stat}");
+            println!();
+            print!("{patch}");
+            println!("Nothing was committed or pushed. Re-run without --dry-run to publish.");
         }
-        let digest = digest::digest_dir(&self.dest_installed)?;
-        self.write_lock_entry(final_commit, digest)?;
+        self.remove_worktree(&mut guard, &wt_path);
         Ok(())
     }
 
-    fn add_worktree(&mut self) -> Result<(PathBuf, WorktreeGuard)> {
+    fn add_worktree(&mut self, detach: bool) -> Result<(PathBuf, WorktreeGuard)> {
         let base = TempDir::new().context("create temp base for worktree")?;
         let wt_path = base.path().join("wt");
-        run(
-            Command::new("git").args([
-                "-C",
-                &self.target.cache_dir.to_string_lossy(),
-                "worktree",
-                "add",
-                "-b",
-                &self.branch_name,
-                wt_path.to_string_lossy().as_ref(),
-                &self.target.commit,
-            ]),
-            "git worktree add",
-        )?;
+        let cache_dir = self.repo().cache_dir.to_string_lossy().to_string();
+        let wt = wt_path.to_string_lossy().to_string();
+        let mut args = vec!["-C", &cache_dir, "worktree", "add"];
+        if detach {
+            args.push("--detach");
+        } else {
+            args.extend(["-b", &self.branch_name]);
+        }
+        args.extend([wt.as_str(), &self.base_commit]);
+        run(Command::new("git").args(&args), "git worktree add")?;
         self.worktree_base = Some(base);
         Ok((
             wt_path.clone(),
-            WorktreeGuard::new(self.target.cache_dir.clone(), wt_path),
+            WorktreeGuard::new(self.repo().cache_dir.clone(), wt_path),
         ))
     }
 
-    fn sync_installed_dir(&self, wt_path: &Path) -> Result<()> {
-        let target_subdir = wt_path.join(&self.target.skill_path);
-        if let Some(parent) = target_subdir.parent() {
-            fs::create_dir_all(parent).ok();
-        }
-        let force_missing_rsync = env::var_os("SK_FORCE_RSYNC_MISSING").is_some();
-        if !force_missing_rsync && which::which("rsync").is_ok() {
-            fs::create_dir_all(&target_subdir)?;
-            run(
-                Command::new("rsync").args([
-                    "-a",
-                    "--delete",
-                    "--exclude",
-                    ".git",
-                    &format!(
-                        "{}/",
-                        self.dest_installed.to_string_lossy().trim_end_matches('/')
-                    ),
-                    &format!("{}/", target_subdir.to_string_lossy().trim_end_matches('/')),
-                ]),
-                "rsync contents",
-            )?
-        } else {
-            eprintln!(
-                "Warning: 'rsync' not found; falling back to a recursive copy for '{}'. Install rsync for faster sync-back runs.",
-                self.args.installed_name
-            );
-            let is_root =
-                self.target.skill_path.trim().is_empty() || self.target.skill_path.trim() == ".";
-            if target_subdir.exists() {
-                if is_root {
-                    purge_children_except_git(&target_subdir).with_context(|| {
-                        format!("purge children in {}", target_subdir.display())
-                    })?;
-                } else {
-                    fs::remove_dir_all(&target_subdir)
-                        .with_context(|| format!("remove {}", target_subdir.display()))?;
-                }
-            }
-            fs::create_dir_all(&target_subdir)?;
-            mirror_dir(&self.dest_installed, &target_subdir).with_context(|| {
-                format!(
-                    "copy {} -> {}",
-                    self.dest_installed.display(),
-                    target_subdir.display()
-                )
-            })?;
-        }
-        Ok(())
-    }
-
-    fn commit_worktree(&self, wt_path: &Path, guard: &mut WorktreeGuard) -> Result<Option<String>> {
+    fn commit_worktree(&self, item: &SyncItem, wt_path: &Path) -> Result<Option<String>> {
         run(
             Command::new("git").args(["-C", wt_path.to_string_lossy().as_ref(), "add", "-A"]),
             "git add",
         )?;
-        let msg = self
-            .args
-            .message
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| default_commit_message(self.args.installed_name));
+        let msg = match self.args.message {
+            Some(msg) if self.items.len() == 1 => msg.to_string(),
+            Some(msg) => format!("{msg} ({})", item.name),
+            None => default_commit_message(&item.name),
+        };
         let commit_out = Command::new("git")
             .args([
                 "-C",
@@ -228,10 +340,9 @@ impl<'a> SyncSession<'a> {
         if no_changes {
             println!(
                 "No changes to commit for '{}': {}",
-                self.args.installed_name,
+                item.name,
                 combined.trim()
             );
-            self.cleanup_noop_branch(wt_path, guard);
             Ok(None)
         } else {
             bail!("git commit failed: {}", combined.trim());
@@ -242,7 +353,7 @@ impl<'a> SyncSession<'a> {
         let removed = match Command::new("git")
             .args([
                 "-C",
-                &self.target.cache_dir.to_string_lossy(),
+                &self.repo().cache_dir.to_string_lossy(),
                 "worktree",
                 "remove",
                 "--force",
@@ -271,7 +382,7 @@ impl<'a> SyncSession<'a> {
             match Command::new("git")
                 .args([
                     "-C",
-                    &self.target.cache_dir.to_string_lossy(),
+                    &self.repo().cache_dir.to_string_lossy(),
                     "branch",
                     "-D",
                     &self.branch_name,
@@ -314,9 +425,11 @@ impl<'a> SyncSession<'a> {
         }
         println!(
             "Pushed branch '{}' to origin for {}/{}.",
-            self.branch_name, self.target.spec.owner, self.target.spec.repo
+            self.branch_name,
+            self.repo().spec.owner,
+            self.repo().spec.repo
         );
-        match automate_pr_flow(wt_path, &self.branch_name, &self.target.spec) {
+        match automate_pr_flow(wt_path, &self.branch_name, &self.repo().spec, &self.forge) {
             Ok(report) => Ok(report),
             Err(err) => {
                 eprintln!(
@@ -332,7 +445,7 @@ impl<'a> SyncSession<'a> {
         let rm_status = Command::new("git")
             .args([
                 "-C",
-                &self.target.cache_dir.to_string_lossy(),
+                &self.repo().cache_dir.to_string_lossy(),
                 "worktree",
                 "remove",
                 "--force",
@@ -354,44 +467,51 @@ impl<'a> SyncSession<'a> {
 
     fn finalize_commit(&self, head: &str, report: Option<&PrAutomationReport>) -> Result<String> {
         if let Some(info) = report {
-            maybe_wait_for_auto_merge(&self.target, info, head, self.args.installed_name)
+            maybe_wait_for_auto_merge(self.repo(), info, head, &self.names())
         } else {
             Ok(head.to_string())
         }
     }
 
-    fn refresh_install(&self, commit: &str) -> Result<()> {
-        refresh_install_from_commit(&self.target, &self.dest_installed, commit)
-    }
-
-    fn write_lock_entry(&mut self, final_commit: String, digest: String) -> Result<()> {
-        let install_name = self.args.installed_name.to_string();
-        let spec = self.target.spec.clone();
-        let skill_path = self.target.skill_path.clone();
-        let lock_index = self.target.lock_index;
+    /// Record every synced skill in one lockfile edit, so a batch lands together.
+    fn write_lock_entries(
+        &mut self,
+        final_commit: &str,
+        updates: Vec<(usize, String, Vec<digest::FileEntry>)>,
+    ) -> Result<()> {
+        let installed_at = Utc::now().to_rfc3339();
+        let entries: Vec<(Option<usize>, lock::LockSkill)> = updates
+            .into_iter()
+            .map(|(idx, digest, files)| {
+                let item = &self.items[idx];
+                let target = &item.target;
+                (
+                    target.lock_index,
+                    lock::LockSkill {
+                        install_name: item.name.clone(),
+                        source: lock::Source::new(target.spec.clone(), target.skill_path.clone()),
+                        git_ref: target.git_ref.clone(),
+                        constraint: target.constraint.clone(),
+                        commit: final_commit.to_string(),
+                        digest,
+                        files,
+                        installed_at: installed_at.clone(),
+                    },
+                )
+            })
+            .collect();
         lock::edit_lockfile(&self.lock_path, |lf| {
-            lf.ensure_repo_entry(&spec);
-            let entry = lock::LockSkill {
-                install_name: install_name.clone(),
-                source: lock::Source::new(spec.clone(), skill_path.clone()),
-                legacy_ref: None,
-                commit: final_commit.clone(),
-                digest: digest.clone(),
-                installed_at: Utc::now().to_rfc3339(),
-            };
-            if let Some(idx) = lock_index {
-                if lf
-                    .skills
-                    .get(idx)
-                    .map(|s| s.install_name == install_name)
-                    .unwrap_or(false)
-                {
-                    lf.skills[idx] = entry;
-                } else {
-                    upsert_lock_entry(lf, entry);
+            for (lock_index, entry) in &entries {
+                lf.ensure_repo_entry(entry.source.repo_spec());
+                let slot = lock_index.filter(|&idx| {
+                    lf.skills
+                        .get(idx)
+                        .is_some_and(|s| s.install_name == entry.install_name)
+                });
+                match slot {
+                    Some(idx) => lf.skills[idx] = entry.clone(),
+                    None => upsert_lock_entry(lf, entry.clone()),
                 }
-            } else {
-                upsert_lock_entry(lf, entry);
             }
             lf.generated_at = Utc::now().to_rfc3339();
             Ok(())
@@ -401,6 +521,85 @@ impl<'a> SyncSession<'a> {
     }
 }
 
+/// Mirror an install into its skill path inside a sync-back worktree.
+fn sync_installed_dir(item: &SyncItem, wt_path: &Path) -> Result<()> {
+    let target_subdir = wt_path.join(&item.target.skill_path);
+    if let Some(parent) = target_subdir.parent() {
+        fs::create_dir_all(parent).ok();
+    }
+    let force_missing_rsync = env::var_os("SK_FORCE_RSYNC_MISSING").is_some();
+    if !force_missing_rsync && which::which("rsync").is_ok() {
+        fs::create_dir_all(&target_subdir)?;
+        // `.skignore` is gitignore syntax, which rsync's own filter rules do
+        // not follow (a leading `!` clears the list, for one), so resolve it
+        // here and hand rsync the exact paths to leave out.
+        let excludes = rsync_exclude_file(&item.dest_installed)?;
+        run(
+            Command::new("rsync").args([
+                "-a",
+                "--delete",
+                "--exclude",
+                ".git",
+                &format!("--exclude-from={}", excludes.path().display()),
+                &format!(
+                    "{}/",
+                    item.dest_installed.to_string_lossy().trim_end_matches('/')
+                ),
+                &format!("{}/", target_subdir.to_string_lossy().trim_end_matches('/')),
+            ]),
+            "rsync contents",
+        )?
+    } else {
+        eprintln!(
+            "Warning: 'rsync' not found; falling back to a recursive copy for '{}'. Install rsync for faster sync-back runs.",
+            item.name
+        );
+        let is_root =
+            item.target.skill_path.trim().is_empty() || item.target.skill_path.trim() == ".";
+        if target_subdir.exists() {
+            if is_root {
+                purge_children_except_git(&target_subdir)
+                    .with_context(|| format!("purge children in {}", target_subdir.display()))?;
+            } else {
+                fs::remove_dir_all(&target_subdir)
+                    .with_context(|| format!("remove {}", target_subdir.display()))?;
+            }
+        }
+        fs::create_dir_all(&target_subdir)?;
+        mirror_dir(&item.dest_installed, &target_subdir).with_context(|| {
+            format!(
+                "copy {} -> {}",
+                item.dest_installed.display(),
+                target_subdir.display()
+            )
+        })?;
+    }
+    Ok(())
+}
+
+/// Anchored rsync exclude patterns for everything `.skignore` leaves out of `dir`.
+fn rsync_exclude_file(dir: &Path) -> Result<NamedTempFile> {
+    let mut file = NamedTempFile::new().context("create rsync exclude list")?;
+    for rel in digest::ignored_paths(dir) {
+        let rel = rel.to_string_lossy().into_owned();
+        // rsync only honors backslash escapes once a pattern has a wildcard.
+        let pattern = if rel.contains(['*', '?', '[']) {
+            rel.chars().fold(String::new(), |mut out, c| {
+                if matches!(c, '*' | '?' | '[' | '\\') {
+                    out.push('\\');
+                }
+                out.push(c);
+                out
+            })
+        } else {
+            rel
+        };
+        writeln!(file, "/{pattern}")?;
+    }
+    file.flush()?;
+    Ok(file)
+}
+
 fn upsert_lock_entry(lf: &mut lock::Lockfile, entry: lock::LockSkill) {
     if let Some(idx) = lf
         .skills
@@ -414,7 +613,156 @@ fn upsert_lock_entry(lf: &mut lock::Lockfile, entry: lock::LockSkill) {
 }
 
 pub fn run_sync_back(args: SyncBackArgs) -> Result<()> {
-    SyncSession::new(args)?.execute()
+    let project_root = git::ensure_git_repo()?;
+    let cfg = config::load_or_default()?;
+    let install_root_rel = args.root.unwrap_or(&cfg.default_root);
+    let install_root = paths::resolve_project_path(&project_root, install_root_rel);
+    let lock_path = project_root.join("skills.lock.json");
+    let lockfile = lock::Lockfile::load_or_empty(&lock_path)?;
+
+    let mut names: Vec<String> = if args.all_modified {
+        modified_installs(&install_root, &lockfile)?
+    } else {
+        args.installed_names.iter().map(|n| n.to_string()).collect()
+    };
+    let mut seen = HashSet::new();
+    names.retain(|name| seen.insert(name.clone()));
+    if names.is_empty() {
+        if args.all_modified {
+            println!("No modified skills to sync back.");
+            return Ok(());
+        }
+        bail!("name at least one installed skill or pass --all-modified");
+    }
+    if names.len() > 1 && args.skill_path.is_some() {
+        bail!("--skill-path only applies when syncing back a single skill");
+    }
+
+    // Group by upstream repo, keeping the order skills were named in.
+    let mut groups: Vec<Vec<SyncItem>> = Vec::new();
+    for name in &names {
+        let item = build_item(&args, &cfg, &lockfile, &install_root, name)?;
+        match groups
+            .iter_mut()
+            .find(|g| g[0].target.cache_dir == item.target.cache_dir)
+        {
+            Some(group) => group.push(item),
+            None => groups.push(vec![item]),
+        }
+    }
+    if args.patch.is_some() && groups.len() > 1 {
+        bail!("--patch writes one file against one repo; these skills come from several repos, so export them separately.");
+    }
+    for items in groups {
+        SyncSession::new(&args, items, lock_path.clone(), cfg.forge.clone())?.execute()?;
+    }
+    Ok(())
+}
+
+fn build_item(
+    args: &SyncBackArgs,
+    cfg: &config::UserConfig,
+    lockfile: &lock::Lockfile,
+    install_root: &Path,
+    name: &str,
+) -> Result<SyncItem> {
+    let dest_installed = install_root.join(name);
+    if !dest_installed.exists() {
+        bail!("installed dir missing for '{name}'. Run 'sk doctor --apply' to rebuild first.");
+    }
+    let lock_index = lockfile.skills.iter().position(|s| s.install_name == name);
+    // Skills imported from a local folder or zip have no upstream yet, so
+    // sync-back publishes them like a new skill and the lock entry switches to git.
+    let tracked = lock_index.filter(|&idx| !lockfile.skills[idx].source.is_local());
+    let target = if let Some(idx) = tracked {
+        build_existing_target(lockfile.skills[idx].clone(), idx, args.patch.is_some())?
+    } else if args.patch.is_some() {
+        bail!("'{name}' has no locked git source to diff against; --patch exports edits to skills installed from a repo.");
+    } else {
+        let repo_value = match args.repo {
+            Some(raw) if !raw.trim().is_empty() => raw.trim().to_string(),
+            _ => {
+                let trimmed = cfg.default_repo.trim();
+                if trimmed.is_empty() {
+                    bail!(
+                        "default_repo is not configured. Run 'sk config set default_repo <repo>' or pass --repo <target> when calling 'sk sync-back {name}'."
+                    );
+                }
+                trimmed.to_string()
+            }
+        };
+        build_new_target(&repo_value, args.skill_path, name, args.https, cfg)?
+    };
+    Ok(SyncItem {
+        name: name.to_string(),
+        dest_installed,
+        target,
+    })
+}
+
+/// Git-sourced installs whose contents no longer match their locked digest.
+fn modified_installs(install_root: &Path, lockfile: &lock::Lockfile) -> Result<Vec<String>> {
+    let mut names = Vec::new();
+    for skill in &lockfile.skills {
+        if skill.source.is_local() {
+            continue;
+        }
+        let dir = install_root.join(&skill.install_name);
+        if !dir.is_dir() {
+            continue;
+        }
+        if digest::digest_dir_versioned(&dir, lockfile.digest_version)? != skill.digest {
+            names.push(skill.install_name.clone());
+        }
+    }
+    Ok(names)
+}
+
+/// Skills in one repo may be locked at different commits. Branch from the
+/// newest of them, but only if none of the older skills changed upstream in
+/// between; otherwise mirroring them would silently revert those changes.
+fn pick_base_commit(items: &[SyncItem]) -> Result<String> {
+    let cache_dir = &items[0].target.cache_dir;
+    let mut base = items[0].target.commit.clone();
+    for item in &items[1..] {
+        let commit = &item.target.commit;
+        if *commit == base || git::is_ancestor(cache_dir, commit, &base)? {
+            continue;
+        }
+        if !git::is_ancestor(cache_dir, &base, commit)? {
+            bail!(
+                "'{}' and '{}' are locked at unrelated commits ({} and {}); sync them back separately.",
+                items[0].name,
+                item.name,
+                short_sha(&base),
+                short_sha(commit)
+            );
+        }
+        base = commit.clone();
+    }
+    for item in items {
+        let target = &item.target;
+        if target.commit != base
+            && git::diff_includes_path(cache_dir, &target.commit, &base, &target.skill_path)?
+        {
+            bail!(
+                "'{}' is locked at {} but upstream changed it by {}; run 'sk upgrade {}' or sync it back on its own.",
+                item.name,
+                short_sha(&target.commit),
+                short_sha(&base),
+                item.name
+            );
+        }
+    }
+    Ok(base)
+}
+
+fn quoted_names(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|n| format!("'{n}'"))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 fn default_branch_name(name: &str) -> String {
@@ -434,6 +782,20 @@ fn default_commit_message(name: &str) -> String {
     format!("sk sync-back: {name} ({ts})")
 }
 
+fn git_stdout(args: &[&str], what: &str) -> Result<String> {
+    let out = Command::new("git")
+        .args(args)
+        .output()
+        .with_context(|| format!("spawn {what}"))?;
+    if !out.status.success() {
+        bail!(
+            "{what} failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
 fn run(cmd: &mut Command, what: &str) -> Result<()> {
     let st = cmd.status().with_context(|| format!("spawn {what}"))?;
     if !st.success() {
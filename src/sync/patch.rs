@@ -0,0 +1,310 @@
+use super::fs_utils::copy_symlink;
+use super::target::build_existing_target;
+use super::{
+    default_commit_message, git_stdout, quoted_names, run, short_sha, sync_installed_dir, SyncItem,
+    SyncSession, WorktreeGuard,
+};
+use crate::{config, git, lock, paths};
+use anyhow::{bail, Context, Result};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::TempDir;
+
+/// How `sync-back --patch` lays out the exported file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PatchFormat {
+    /// One format-patch entry covering every exported skill.
+    Patch,
+    /// The full series, one format-patch entry per skill commit.
+    Mbox,
+}
+
+impl PatchFormat {
+    fn parse(raw: Option<&str>) -> Result<Self> {
+        match raw.map(|r| r.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("patch") => Ok(PatchFormat::Patch),
+            Some("mbox") => Ok(PatchFormat::Mbox),
+            Some(other) => bail!("unknown patch format '{other}' (expected patch or mbox)"),
+        }
+    }
+}
+
+impl SyncSession<'_> {
+    /// Commit the installs in a detached worktree and write them out with
+    /// `git format-patch` instead of pushing.
+    pub(super) fn export_patch(mut self, out: &Path) -> Result<()> {
+        let format = PatchFormat::parse(self.args.patch_format)?;
+        let (wt_path, mut guard) = self.add_worktree(true)?;
+        let mut commits = 0;
+        for item in &self.items {
+            sync_installed_dir(item, &wt_path)?;
+            if self.commit_worktree(item, &wt_path)?.is_some() {
+                commits += 1;
+            }
+        }
+        if commits == 0 {
+            println!("No changes to export for {}.", quoted_names(&self.names()));
+            self.remove_worktree(&mut guard, &wt_path);
+            return Ok(());
+        }
+
+        let wt = wt_path.to_string_lossy().to_string();
+        if format == PatchFormat::Patch && commits > 1 {
+            let msg = match self.args.message {
+                Some(msg) => msg.to_string(),
+                None => default_commit_message(&self.names().join(", ")),
+            };
+            run(
+                Command::new("git").args(["-C", &wt, "reset", "--soft", &self.base_commit]),
+                "git reset --soft",
+            )?;
+            run(
+                Command::new("git").args(["-C", &wt, "commit", "-q", "-m", &msg]),
+                "git commit",
+            )?;
+        }
+        let range = format!("{}..HEAD", self.base_commit);
+        let body = git_stdout(
+            &["-C", &wt, "format-patch", "--stdout", "--binary", &range],
+            "git format-patch",
+        )?;
+        self.remove_worktree(&mut guard, &wt_path);
+
+        if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(out, body).with_context(|| format!("write {}", out.display()))?;
+        let spec = &self.repo().spec;
+        println!(
+            "Wrote edits for {} to {} (against {}/{} at {}). Apply them with 'sk apply-patch <name> {}'.",
+            quoted_names(&self.names()),
+            out.display(),
+            spec.owner,
+            spec.repo,
+            short_sha(&self.base_commit),
+            out.display()
+        );
+        Ok(())
+    }
+}
+
+pub struct ApplyPatchArgs<'a> {
+    pub installed_name: &'a str,
+    pub patch_file: &'a str,
+    pub root: Option<&'a str>,
+}
+
+/// Apply a patch produced by `sync-back --patch` (or plain `git diff` output)
+/// to an installed skill. The patch is applied in a worktree at the locked
+/// commit on top of the install's current files, so local edits are kept and
+/// conflicts are caught before anything on disk changes.
+pub fn run_apply_patch(args: ApplyPatchArgs) -> Result<()> {
+    let project_root = git::ensure_git_repo()?;
+    let cfg = config::load_or_default()?;
+    let install_root_rel = args.root.unwrap_or(&cfg.default_root);
+    let install_root = paths::resolve_project_path(&project_root, install_root_rel);
+    let name = args.installed_name;
+    let dest_installed = install_root.join(name);
+    if !dest_installed.is_dir() {
+        bail!("installed dir missing for '{name}'. Run 'sk doctor --apply' to rebuild first.");
+    }
+    let lockfile = lock::Lockfile::load_or_empty(&project_root.join("skills.lock.json"))?;
+    let idx = lockfile
+        .skills
+        .iter()
+        .position(|s| s.install_name == name && !s.source.is_local())
+        .ok_or_else(|| {
+            anyhow::anyhow!("'{name}' has no locked git commit to apply a patch against")
+        })?;
+    let patch_path = absolute(Path::new(args.patch_file))?;
+    if !patch_path.is_file() {
+        bail!("patch file '{}' not found", args.patch_file);
+    }
+    let item = SyncItem {
+        name: name.to_string(),
+        target: build_existing_target(lockfile.skills[idx].clone(), idx, true)?,
+        dest_installed,
+    };
+    let skill_path = item.target.skill_path.clone();
+    let patch_arg = patch_path.to_string_lossy().to_string();
+
+    let base = TempDir::new().context("create temp base for worktree")?;
+    let wt_path = base.path().join("wt");
+    let wt = wt_path.to_string_lossy().to_string();
+    let cache_dir = item.target.cache_dir.to_string_lossy().to_string();
+    run(
+        Command::new("git").args([
+            "-C",
+            &cache_dir,
+            "worktree",
+            "add",
+            "--detach",
+            &wt,
+            &item.target.commit,
+        ]),
+        "git worktree add",
+    )?;
+    let _guard = WorktreeGuard::new(item.target.cache_dir.clone(), wt_path.clone());
+
+    for path in patched_paths(&wt, &patch_arg)? {
+        if !within(&path, &skill_path) {
+            bail!("patch touches '{path}', which is outside '{name}' ({skill_path}) in the repo");
+        }
+    }
+
+    // Start from what is installed so the patch lands on top of local edits.
+    sync_installed_dir(&item, &wt_path)?;
+    run(
+        Command::new("git").args(["-C", &wt, "add", "-A"]),
+        "git add",
+    )?;
+    run(
+        Command::new("git").args([
+            "-C",
+            &wt,
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "sk apply-patch: installed state",
+        ]),
+        "git commit",
+    )?;
+    let before = git::rev_parse(&wt_path, "HEAD")?;
+
+    let is_mbox = fs::read(&patch_path)
+        .map(|bytes| bytes.starts_with(b"From "))
+        .unwrap_or(false);
+    let applied = if is_mbox {
+        Command::new("git")
+            .args(["-C", &wt, "am", "--3way", "--keep-cr", &patch_arg])
+            .output()
+    } else {
+        Command::new("git")
+            .args(["-C", &wt, "apply", "--3way", &patch_arg])
+            .output()
+    }
+    .context("spawn git to apply the patch")?;
+    if !applied.status.success() {
+        let conflicts = git_stdout(
+            &["-C", &wt, "diff", "--name-only", "--diff-filter=U"],
+            "git diff --diff-filter=U",
+        )
+        .unwrap_or_default();
+        if is_mbox {
+            let _ = Command::new("git")
+                .args(["-C", &wt, "am", "--abort"])
+                .status();
+        }
+        let files: Vec<&str> = conflicts.lines().filter(|l| !l.trim().is_empty()).collect();
+        if files.is_empty() {
+            bail!(
+                "patch does not apply to '{name}': {}",
+                String::from_utf8_lossy(&applied.stderr).trim()
+            );
+        }
+        bail!(
+            "patch conflicts with '{name}' in:\n  {}\nThe install was left untouched.",
+            files.join("\n  ")
+        );
+    }
+    if !is_mbox {
+        run(
+            Command::new("git").args(["-C", &wt, "add", "-A"]),
+            "git add",
+        )?;
+        run(
+            Command::new("git").args([
+                "-C",
+                &wt,
+                "commit",
+                "-q",
+                "--allow-empty",
+                "-m",
+                "sk apply-patch",
+            ]),
+            "git commit",
+        )?;
+    }
+
+    let changed = git_stdout(
+        &[
+            "-C",
+            &wt,
+            "diff",
+            "--name-only",
+            "--no-renames",
+            &before,
+            "HEAD",
+            "--",
+            &skill_path,
+        ],
+        "git diff --name-only",
+    )?;
+    let mut count = 0;
+    for path in changed.lines().filter(|l| !l.trim().is_empty()) {
+        let rel = relative_to_skill(path, &skill_path);
+        let src = wt_path.join(path);
+        let dest = item.dest_installed.join(rel);
+        match fs::symlink_metadata(&src) {
+            Ok(meta) if meta.file_type().is_symlink() => copy_symlink(&src, &dest)?,
+            Ok(_) => {
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(&src, &dest)
+                    .with_context(|| format!("copy {} -> {}", src.display(), dest.display()))?;
+            }
+            Err(_) => {
+                if dest.exists() || dest.is_symlink() {
+                    fs::remove_file(&dest).with_context(|| format!("remove {}", dest.display()))?;
+                }
+            }
+        }
+        count += 1;
+    }
+    println!(
+        "Applied {} to '{name}' ({count} file(s) changed). These are local edits: review them with 'sk doctor --diff {name}' and publish with 'sk sync-back {name}'.",
+        args.patch_file
+    );
+    Ok(())
+}
+
+fn patched_paths(wt: &str, patch: &str) -> Result<Vec<String>> {
+    let numstat = git_stdout(
+        &["-C", wt, "apply", "--numstat", patch],
+        "git apply --numstat",
+    )?;
+    Ok(numstat
+        .lines()
+        .filter_map(|line| line.splitn(3, '\t').nth(2))
+        .map(|path| path.to_string())
+        .collect())
+}
+
+fn within(path: &str, skill_path: &str) -> bool {
+    skill_path == "."
+        || path
+            .strip_prefix(skill_path)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn relative_to_skill<'p>(path: &'p str, skill_path: &str) -> &'p str {
+    if skill_path == "." {
+        path
+    } else {
+        path.strip_prefix(skill_path)
+            .map(|rest| rest.trim_start_matches('/'))
+            .unwrap_or(path)
+    }
+}
+
+fn absolute(path: &Path) -> Result<PathBuf> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(env::current_dir()?.join(path))
+    }
+}
@@ -1,17 +1,16 @@
-use super::short_sha;
+use super::forge::{self, AutoMergeOutcome, Forge, PrInfo};
 use super::target::SyncTarget;
+use super::{quoted_names, short_sha};
 use crate::git;
-use anyhow::{anyhow, bail, Context, Result};
-use serde::Deserialize;
-use serde_json::Value as JsonValue;
+use anyhow::{anyhow, Result};
 use std::env;
 use std::path::Path;
-use std::process::Command;
 use std::thread;
 use std::time::{Duration, Instant};
 
 pub(crate) struct PrAutomationReport {
-    pub(crate) pr: GhPrInfo,
+    pub(crate) forge: Box<dyn Forge>,
+    pub(crate) pr: PrInfo,
     pub(crate) auto_merge_armed: bool,
 }
 
@@ -19,28 +18,31 @@ pub(super) fn automate_pr_flow(
     wt_path: &Path,
     branch_name: &str,
     spec: &git::RepoSpec,
+    forge_setting: &str,
 ) -> Result<Option<PrAutomationReport>> {
-    let gh_missing = env::var_os("SK_FORCE_GH_MISSING").is_some() || which::which("gh").is_err();
-    if gh_missing {
-        eprintln!(
-            "Warning: skipping PR automation because the GitHub CLI ('gh') is unavailable. Push completed; open a PR manually or install gh from https://cli.github.com/."
-        );
+    let forge = forge::select(spec, forge_setting)?;
+    if let Some(warning) = forge.unavailable() {
+        eprintln!("{warning}");
         return Ok(None);
     }
 
-    let repo_selector = format_repo_selector(spec);
-    let (pr, created) = ensure_pull_request(&repo_selector, wt_path, branch_name)?;
+    let (pr, created) = ensure_pull_request(forge.as_ref(), wt_path, branch_name)?;
     if created {
         println!("Opened PR {} for branch '{branch_name}'.", pr.url);
     } else {
         println!("Reusing PR {} for branch '{branch_name}'.", pr.url);
     }
 
-    let outcome = auto_merge_pull_request(&repo_selector, wt_path, &pr);
+    let outcome = if pr.conflicted {
+        AutoMergeOutcome::Conflict
+    } else {
+        forge.arm_auto_merge(wt_path, &pr)
+    };
     match &outcome {
         AutoMergeOutcome::Armed => {
             println!(
-                "Auto-merge armed; GitHub will land {} once required checks pass.",
+                "Auto-merge armed; {} will land {} once required checks pass.",
+                forge.name(),
                 pr.url
             );
         }
@@ -52,15 +54,16 @@ pub(super) fn automate_pr_flow(
         }
         AutoMergeOutcome::Skipped(reason) => {
             println!("Auto-merge skipped for {} ({reason}).", pr.url);
-            if let Some(tip) = auto_merge_tip(reason, spec) {
+            if let Some(tip) = forge.auto_merge_tip(reason) {
                 println!("{tip}");
             }
         }
     }
 
     Ok(Some(PrAutomationReport {
-        pr,
         auto_merge_armed: matches!(outcome, AutoMergeOutcome::Armed),
+        forge,
+        pr,
     }))
 }
 
@@ -68,11 +71,15 @@ pub(super) fn maybe_wait_for_auto_merge(
     target: &SyncTarget,
     report: &PrAutomationReport,
     pushed_head: &str,
-    install_name: &str,
+    install_names: &[String],
 ) -> Result<String> {
     if !report.auto_merge_armed {
         return Ok(pushed_head.to_string());
     }
+    let upgrade_target = match install_names {
+        [single] => single.as_str(),
+        _ => "--all",
+    };
     let timeout = auto_merge_timeout();
     let poll = auto_merge_poll_interval();
     let seconds = timeout.as_secs();
@@ -95,17 +102,17 @@ pub(super) fn maybe_wait_for_auto_merge(
         }
         Ok(None) => {
             println!(
-                "Auto-merge for '{}' has not finished yet; keeping lock at {}. Run 'sk upgrade {}' after the PR merges.",
-                install_name,
+                "Auto-merge for {} has not finished yet; keeping lock at {}. Run 'sk upgrade {}' after the PR merges.",
+                quoted_names(install_names),
                 short_sha(pushed_head),
-                install_name
+                upgrade_target
             );
             Ok(pushed_head.to_string())
         }
         Err(err) => {
             eprintln!(
-                "Warning: unable to confirm merged commit for '{}': {err:#}. Keeping lock at {}.",
-                install_name,
+                "Warning: unable to confirm merged commit for {}: {err:#}. Keeping lock at {}.",
+                quoted_names(install_names),
                 short_sha(pushed_head)
             );
             Ok(pushed_head.to_string())
@@ -114,149 +121,21 @@ pub(super) fn maybe_wait_for_auto_merge(
 }
 
 fn ensure_pull_request(
-    repo_selector: &str,
+    forge: &dyn Forge,
     wt_path: &Path,
     branch_name: &str,
-) -> Result<(GhPrInfo, bool)> {
-    if let Some(existing) = find_existing_pr(repo_selector, wt_path, branch_name)? {
+) -> Result<(PrInfo, bool)> {
+    if let Some(existing) = forge.find_pr(wt_path, branch_name)? {
         return Ok((existing, false));
     }
-    create_pull_request(repo_selector, wt_path, branch_name)?;
-    let created = find_existing_pr(repo_selector, wt_path, branch_name)?
-        .ok_or_else(|| anyhow!("gh pr create succeeded but no PR was found"))?;
-    Ok((created, true))
-}
-
-fn find_existing_pr(
-    repo_selector: &str,
-    wt_path: &Path,
-    branch_name: &str,
-) -> Result<Option<GhPrInfo>> {
-    let out = Command::new("gh")
-        .current_dir(wt_path)
-        .args([
-            "pr",
-            "list",
-            "--state",
-            "all",
-            "--head",
-            branch_name,
-            "--limit",
-            "1",
-            "--json",
-            "number,url,mergeStateStatus,mergeable",
-            "-R",
-            repo_selector,
-        ])
-        .output()
-        .context("run gh pr list")?;
-    if !out.status.success() {
-        bail!(
-            "gh pr list failed: {}",
-            format_gh_failure(&out.stdout, &out.stderr)
-        );
-    }
-    let mut entries: Vec<GhPrInfo> =
-        serde_json::from_slice(&out.stdout).context("parse gh pr list JSON output")?;
-    Ok(entries.pop())
-}
-
-fn create_pull_request(repo_selector: &str, wt_path: &Path, branch_name: &str) -> Result<()> {
-    let out = Command::new("gh")
-        .current_dir(wt_path)
-        .args([
-            "pr",
-            "create",
-            "--fill",
-            "--head",
-            branch_name,
-            "-R",
-            repo_selector,
-        ])
-        .output()
-        .context("run gh pr create")?;
-    if !out.status.success() {
-        bail!(
-            "gh pr create failed: {}",
-            format_gh_failure(&out.stdout, &out.stderr)
-        );
-    }
-    Ok(())
-}
-
-fn auto_merge_pull_request(repo_selector: &str, wt_path: &Path, pr: &GhPrInfo) -> AutoMergeOutcome {
-    if is_pr_conflicted(pr) {
-        return AutoMergeOutcome::Conflict;
-    }
-
-    let number = pr.number.to_string();
-    match Command::new("gh")
-        .current_dir(wt_path)
-        .args([
-            "pr",
-            "merge",
-            &number,
-            "--auto",
-            "--merge",
-            "-R",
-            repo_selector,
-        ])
-        .output()
-    {
-        Ok(out) if out.status.success() => AutoMergeOutcome::Armed,
-        Ok(out) => AutoMergeOutcome::Skipped(format_gh_failure(&out.stdout, &out.stderr)),
-        Err(err) => AutoMergeOutcome::Skipped(err.to_string()),
-    }
-}
-
-fn is_pr_conflicted(pr: &GhPrInfo) -> bool {
-    matches!(
-        pr.merge_state_status
-            .as_deref()
-            .map(|s| s.eq_ignore_ascii_case("dirty")),
-        Some(true)
-    ) || matches!(
-        pr.mergeable
-            .as_deref()
-            .map(|s| s.eq_ignore_ascii_case("conflicting")),
-        Some(true)
-    )
-}
-
-fn format_repo_selector(spec: &git::RepoSpec) -> String {
-    if spec.host.is_empty() {
-        format!("{}/{}", spec.owner, spec.repo)
-    } else {
-        format!("{}/{}/{}", spec.host, spec.owner, spec.repo)
-    }
-}
-
-fn auto_merge_tip(reason: &str, spec: &git::RepoSpec) -> Option<String> {
-    if !reason
-        .to_ascii_lowercase()
-        .contains("enablepullrequestautomerge")
-    {
-        return None;
-    }
-
-    let repo_slug = format!("{}/{}", spec.owner, spec.repo);
-    let cmd = if spec.host.is_empty() || spec.host.eq_ignore_ascii_case("github.com") {
-        format!("gh repo edit {repo_slug} --enable-auto-merge")
-    } else {
-        format!(
-            "gh repo edit -R {}/{repo_slug} --enable-auto-merge",
-            spec.host
+    forge.create_pr(wt_path, branch_name)?;
+    let created = forge.find_pr(wt_path, branch_name)?.ok_or_else(|| {
+        anyhow!(
+            "created a {} PR for '{branch_name}' but could not find it afterwards",
+            forge.name()
         )
-    };
-    let host = if spec.host.is_empty() {
-        "github.com"
-    } else {
-        spec.host.as_str()
-    };
-    let settings_url = format!("https://{host}/{repo_slug}/settings");
-    Some(format!(
-        "Tip: enable auto-merge with `{cmd}` or toggle Auto-merge under Settings → General ({settings_url})."
-    ))
+    })?;
+    Ok((created, true))
 }
 
 fn wait_for_merge_commit(
@@ -265,18 +144,17 @@ fn wait_for_merge_commit(
     timeout: Duration,
     poll: Duration,
 ) -> Result<Option<String>> {
-    let repo_selector = format_repo_selector(&target.spec);
     let start = Instant::now();
     loop {
-        let status = query_pr_merge_status(&repo_selector, report.pr.number)?;
-        if status.is_merged() {
-            if let Some(commit) = status.merge_commit_oid() {
+        let status = report.forge.merge_status(&report.pr)?;
+        if status.merged {
+            if let Some(commit) = status.merge_commit {
                 git::ensure_cached_repo(&target.cache_dir, &target.spec)?;
                 if git::has_object(&target.cache_dir, &commit)? {
                     return Ok(Some(commit));
                 }
             }
-        } else if status.is_closed() {
+        } else if status.closed {
             return Ok(None);
         }
         if start.elapsed() >= timeout {
@@ -290,30 +168,6 @@ fn wait_for_merge_commit(
     }
 }
 
-fn query_pr_merge_status(repo_selector: &str, number: u64) -> Result<GhPrMergeStatus> {
-    let out = Command::new("gh")
-        .args([
-            "pr",
-            "view",
-            &number.to_string(),
-            "--json",
-            "state,mergeCommit",
-            "-R",
-            repo_selector,
-        ])
-        .output()
-        .context("run gh pr view")?;
-    if !out.status.success() {
-        bail!(
-            "gh pr view failed: {}",
-            format_gh_failure(&out.stdout, &out.stderr)
-        );
-    }
-    let status: GhPrMergeStatus =
-        serde_json::from_slice(&out.stdout).context("parse gh pr view JSON output")?;
-    Ok(status)
-}
-
 fn auto_merge_timeout() -> Duration {
     duration_from_env_ms("SK_SYNC_BACK_AUTO_MERGE_TIMEOUT_MS", 120_000)
 }
@@ -329,77 +183,3 @@ fn duration_from_env_ms(key: &str, default_ms: u64) -> Duration {
         .map(Duration::from_millis)
         .unwrap_or_else(|| Duration::from_millis(default_ms))
 }
-
-#[derive(Debug, Deserialize)]
-pub(crate) struct GhPrInfo {
-    pub(crate) number: u64,
-    pub(crate) url: String,
-    #[serde(rename = "mergeStateStatus")]
-    pub(crate) merge_state_status: Option<String>,
-    pub(crate) mergeable: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct GhPrMergeStatus {
-    state: Option<String>,
-    #[serde(rename = "mergeCommit")]
-    merge_commit: Option<JsonValue>,
-}
-
-impl GhPrMergeStatus {
-    fn is_merged(&self) -> bool {
-        matches!(
-            self.state
-                .as_deref()
-                .map(|s| s.eq_ignore_ascii_case("merged")),
-            Some(true)
-        )
-    }
-
-    fn is_closed(&self) -> bool {
-        matches!(
-            self.state
-                .as_deref()
-                .map(|s| s.eq_ignore_ascii_case("closed")),
-            Some(true)
-        )
-    }
-
-    fn merge_commit_oid(&self) -> Option<String> {
-        self.merge_commit.as_ref().and_then(|value| match value {
-            JsonValue::String(s) if !s.is_empty() => Some(s.clone()),
-            JsonValue::Object(map) => map
-                .get("oid")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_string())
-                .or_else(|| {
-                    map.get("sha")
-                        .and_then(|v| v.as_str())
-                        .map(|s| s.to_string())
-                }),
-            _ => None,
-        })
-    }
-}
-
-enum AutoMergeOutcome {
-    Armed,
-    Conflict,
-    Skipped(String),
-}
-
-fn format_gh_failure(stdout: &[u8], stderr: &[u8]) -> String {
-    let mut combined = String::new();
-    if !stderr.is_empty() {
-        combined.push_str(&String::from_utf8_lossy(stderr));
-    }
-    if !stdout.is_empty() {
-        combined.push_str(&String::from_utf8_lossy(stdout));
-    }
-    let trimmed = combined.trim();
-    if trimmed.is_empty() {
-        "gh command failed".to_string()
-    } else {
-        trimmed.to_string()
-    }
-}
@@ -7,14 +7,25 @@ pub(crate) struct SyncTarget {
     pub(crate) cache_dir: PathBuf,
     pub(crate) commit: String,
     pub(crate) skill_path: String,
+    pub(crate) git_ref: Option<String>,
+    pub(crate) constraint: Option<String>,
     pub(crate) lock_index: Option<usize>,
 }
 
-pub(super) fn build_existing_target(entry: lock::LockSkill, index: usize) -> Result<SyncTarget> {
+/// With `offline`, a cache that already holds the locked commit is used as-is
+/// rather than fetched, so patch export works without reaching the remote.
+pub(super) fn build_existing_target(
+    entry: lock::LockSkill,
+    index: usize,
+    offline: bool,
+) -> Result<SyncTarget> {
     let spec = entry.source.repo_spec_owned();
     let cache_dir =
         paths::resolve_or_primary_cache_path(&spec.url, &spec.host, &spec.owner, &spec.repo);
-    git::ensure_cached_repo(&cache_dir, &spec)?;
+    let cached = offline && cache_dir.exists() && git::has_object(&cache_dir, &entry.commit)?;
+    if !cached {
+        git::ensure_cached_repo(&cache_dir, &spec)?;
+    }
     if !git::has_object(&cache_dir, &entry.commit)? {
         let short = &entry.commit[..entry.commit.len().min(7)];
         bail!(
@@ -28,6 +39,8 @@ pub(super) fn build_existing_target(entry: lock::LockSkill, index: usize) -> Res
         cache_dir,
         commit: entry.commit,
         skill_path: entry.source.skill_path().to_string(),
+        git_ref: entry.git_ref,
+        constraint: entry.constraint,
         lock_index: Some(index),
     })
 }
@@ -54,6 +67,8 @@ pub(super) fn build_new_target(
         cache_dir,
         commit,
         skill_path,
+        git_ref: None,
+        constraint: None,
         lock_index: None,
     })
 }
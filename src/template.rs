@@ -54,13 +54,13 @@ pub fn run_template_create(args: TemplateCreateArgs) -> Result<()> {
     Ok(())
 }
 
-struct TemplateSource {
-    repo_input: String,
+pub(crate) struct TemplateSource {
+    pub(crate) repo_input: String,
     skill_selector: String,
 }
 
 impl TemplateSource {
-    fn parse(raw: &str) -> Result<Self> {
+    pub(crate) fn parse(raw: &str) -> Result<Self> {
         let trimmed = raw.trim();
         if trimmed.is_empty() {
             bail!("template_source is empty. Set it via 'sk config set template_source <repo> <skill>'.");
@@ -82,7 +82,7 @@ impl TemplateSource {
     }
 }
 
-fn validate_skill_name(input: &str) -> Result<()> {
+pub(crate) fn validate_skill_name(input: &str) -> Result<()> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
         bail!("skill name must not be empty");
@@ -8,12 +8,16 @@ use std::path::PathBuf;
 pub fn refresh_lockfile_caches(lockfile: &lock::Lockfile) -> Result<usize> {
     // gather unique repos by host/owner/repo/url so we refresh each cache once
     let mut uniq = BTreeSet::new();
-    for s in &lockfile.skills {
+    for s in lockfile.skills.iter().filter(|s| !s.source.is_local()) {
         let spec = s.source.repo_spec_owned();
         uniq.insert((spec.url, spec.host, spec.owner, spec.repo));
     }
     if uniq.is_empty() {
-        println!("Lockfile has no skills; cache refresh complete.");
+        if lockfile.skills.is_empty() {
+            println!("Lockfile has no skills; cache refresh complete.");
+        } else {
+            println!("Lockfile has no git-sourced skills to refresh; cache refresh complete.");
+        }
         return Ok(0);
     }
 
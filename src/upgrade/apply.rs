@@ -1,5 +1,5 @@
-use super::fsops::copy_dir_all;
-use super::plan::{SkippedUpgrade, StagedUpgrade, UpgradeTask};
+use super::fsops::{carry_over_ignored, copy_dir_all};
+use super::plan::{LockUpdate, SkippedUpgrade, StagedUpgrade, UpgradeTask};
 use crate::{digest, install, lock};
 use anyhow::{anyhow, bail, Context, Result};
 use chrono::Utc;
@@ -8,7 +8,11 @@ use std::path::Path;
 use std::process::Command;
 use tempfile::tempdir;
 
-pub fn stage_upgrades(staging_root: &Path, tasks: &[UpgradeTask]) -> Result<Vec<StagedUpgrade>> {
+pub fn stage_upgrades(
+    staging_root: &Path,
+    tasks: &[UpgradeTask],
+    digest_version: u32,
+) -> Result<Vec<StagedUpgrade>> {
     let mut staged = Vec::new();
     for task in tasks {
         let staged_path = staging_root.join(&task.install_name);
@@ -19,17 +23,20 @@ pub fn stage_upgrades(staging_root: &Path, tasks: &[UpgradeTask]) -> Result<Vec<
             &task.skill_path,
             &staged_path,
         )?;
-        let new_digest = digest::digest_dir(&staged_path)?;
+        carry_over_ignored(&task.dest, &staged_path)?;
+        let new_digest = digest::digest_dir_versioned(&staged_path, digest_version)?;
+        let new_files = digest::manifest_dir(&staged_path)?;
         staged.push(StagedUpgrade {
             task: task.clone(),
             staged_path,
             new_digest,
+            new_files,
         });
     }
     Ok(staged)
 }
 
-pub fn apply_staged_upgrades(staged: &[StagedUpgrade]) -> Result<Vec<(String, String, String)>> {
+pub fn apply_staged_upgrades(staged: &[StagedUpgrade]) -> Result<Vec<LockUpdate>> {
     let simulate_exdev = std::env::var("SK_SIMULATE_EXDEV").ok().as_deref() == Some("1");
     let fail_after_first = std::env::var("SK_FAIL_AFTER_FIRST_SWAP").ok().as_deref() == Some("1");
     let mut updates = Vec::new();
@@ -37,11 +44,12 @@ pub fn apply_staged_upgrades(staged: &[StagedUpgrade]) -> Result<Vec<(String, St
 
     for (idx, item) in staged.iter().enumerate() {
         let backup = apply_single(item, simulate_exdev)?;
-        updates.push((
-            item.task.install_name.clone(),
-            item.task.new_commit.clone(),
-            item.new_digest.clone(),
-        ));
+        updates.push(LockUpdate {
+            install_name: item.task.install_name.clone(),
+            commit: item.task.new_commit.clone(),
+            digest: item.new_digest.clone(),
+            files: item.new_files.clone(),
+        });
         applied.push((
             item.task.install_name.clone(),
             item.task.dest.clone(),
@@ -115,18 +123,20 @@ fn rollback_applied(applied: Vec<(String, std::path::PathBuf, std::path::PathBuf
     }
 }
 
-pub fn apply_updates_to_lockfile(
-    lf: &mut lock::Lockfile,
-    updates: &[(String, String, String)],
-) -> Result<()> {
+pub fn apply_updates_to_lockfile(lf: &mut lock::Lockfile, updates: &[LockUpdate]) -> Result<()> {
     let now = Utc::now().to_rfc3339();
-    for (name, commit, digest_value) in updates {
-        if let Some(entry) = lf.skills.iter_mut().find(|s| s.install_name == *name) {
-            entry.commit = commit.clone();
-            entry.digest = digest_value.clone();
+    for update in updates {
+        if let Some(entry) = lf
+            .skills
+            .iter_mut()
+            .find(|s| s.install_name == update.install_name)
+        {
+            entry.commit = update.commit.clone();
+            entry.digest = update.digest.clone();
+            entry.files = update.files.clone();
             entry.installed_at = now.clone();
         } else {
-            bail!("lockfile missing entry for {}", name);
+            bail!("lockfile missing entry for {}", update.install_name);
         }
     }
     lf.generated_at = now;
@@ -142,11 +152,12 @@ pub fn print_skipped(skipped: &[SkippedUpgrade]) {
         match &entry.span {
             Some(span) => {
                 println!(
-                    "- {name}: local edits plus upstream update ({} -> {}). Run 'sk sync-back {name}' or revert changes, then rerun 'sk upgrade {name}'.",
+                    "- {name}: local edits plus upstream update ({} -> {}). Run 'sk upgrade {name} --merge' to merge it, or 'sk sync-back {name}'/revert changes, then rerun 'sk upgrade {name}'.",
                     short_sha(&span.current),
                     short_sha(&span.available),
                     name = entry.install_name
                 );
+                print_local_changes(entry);
                 match render_local_vs_upstream_diff(entry) {
                     Ok(diff) if !diff.is_empty() => {
                         println!("  Diff local vs upstream {}:", short_sha(&span.available));
@@ -162,10 +173,21 @@ pub fn print_skipped(skipped: &[SkippedUpgrade]) {
                     }
                 }
             }
-            None => println!(
-                "- {name}: local edits (already at locked commit). Run 'sk sync-back {name}' or revert changes before upgrading.",
-                name = entry.install_name
-            ),
+            None => {
+                println!(
+                    "- {name}: local edits (already at locked commit). Run 'sk sync-back {name}' or revert changes before upgrading.",
+                    name = entry.install_name
+                );
+                print_local_changes(entry);
+            }
+        }
+    }
+}
+
+fn print_local_changes(entry: &SkippedUpgrade) {
+    if let Some(changes) = &entry.local_changes {
+        for line in changes.describe() {
+            println!("  {line}");
         }
     }
 }
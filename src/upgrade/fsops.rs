@@ -1,8 +1,10 @@
+use crate::digest;
 #[cfg(not(any(unix, windows)))]
 use anyhow::bail;
 use anyhow::{anyhow, Context, Result};
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
     let fail_copy = std::env::var("SK_FAIL_COPY").ok().as_deref() == Some("1");
@@ -31,6 +33,34 @@ pub fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Copy files a `.skignore` excludes (build output, caches) from the current
+/// install into a freshly staged tree so upgrades don't discard them. Anything
+/// the staged tree already has, or would no longer ignore, is left alone.
+pub fn carry_over_ignored(current: &Path, staged: &Path) -> Result<()> {
+    let mut copied: Vec<PathBuf> = Vec::new();
+    for rel in digest::ignored_files(current) {
+        let target = staged.join(&rel);
+        if target.exists() {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let source = current.join(&rel);
+        fs::copy(&source, &target)
+            .with_context(|| format!("copy {} -> {}", source.display(), target.display()))?;
+        copied.push(rel);
+    }
+    if copied.is_empty() {
+        return Ok(());
+    }
+    let tracked: HashSet<PathBuf> = digest::list_files(staged).into_iter().collect();
+    for rel in copied.iter().filter(|rel| tracked.contains(*rel)) {
+        fs::remove_file(staged.join(rel))?;
+    }
+    Ok(())
+}
+
 #[cfg(unix)]
 fn copy_symlink(src: &Path, dest: &Path) -> Result<()> {
     use std::os::unix::fs::symlink;
@@ -0,0 +1,393 @@
+use super::apply::apply_updates_to_lockfile;
+use super::fsops::copy_dir_all;
+use super::plan::{ensure_upgradable, LockUpdate};
+use crate::{digest, git, install, lock, paths};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tempfile::tempdir;
+
+const STATE_FILE: &str = "state.json";
+const BACKUP_DIR: &str = "ours";
+const UPSTREAM_SUFFIX: &str = ".upstream";
+
+/// Recorded under `.git/sk-merge/<install-name>/` while conflicts are pending,
+/// alongside a copy of the pre-merge install so `--abort` can put it back.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MergeState {
+    install_name: String,
+    base_commit: String,
+    new_commit: String,
+    /// Digest of the upstream tree at `new_commit`. The lockfile records this
+    /// rather than the merged tree so the install still reads as locally modified.
+    upstream_digest: String,
+    upstream_files: Vec<digest::FileEntry>,
+    conflicts: Vec<Conflict>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Conflict {
+    path: String,
+    kind: ConflictKind,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+enum ConflictKind {
+    /// Conflict markers were written into the file.
+    Content,
+    /// Binary or add/add clash; the upstream copy sits next to it as `<path>.upstream`.
+    Sidecar,
+    /// Edited locally but deleted upstream; the local copy was kept.
+    DeletedUpstream,
+}
+
+impl ConflictKind {
+    fn describe(self) -> &'static str {
+        match self {
+            ConflictKind::Content => "conflict markers",
+            ConflictKind::Sidecar => "upstream copy saved as .upstream",
+            ConflictKind::DeletedUpstream => "deleted upstream, local copy kept",
+        }
+    }
+}
+
+pub struct MergeContext<'a> {
+    pub project_root: &'a Path,
+    pub install_root: &'a Path,
+    pub lock_path: &'a Path,
+    pub install_name: &'a str,
+}
+
+pub fn run_merge(ctx: &MergeContext) -> Result<()> {
+    let state_dir = state_dir(ctx)?;
+    if state_dir.join(STATE_FILE).exists() {
+        bail!(
+            "a merge is already in progress for '{name}'. Run 'sk upgrade {name} --continue' or 'sk upgrade {name} --abort'.",
+            name = ctx.install_name
+        );
+    }
+    let lf = lock::Lockfile::load(ctx.lock_path)?;
+    let skill = lf
+        .skills
+        .iter()
+        .find(|s| s.install_name == ctx.install_name)
+        .ok_or_else(|| anyhow!("skill not found: {}", ctx.install_name))?;
+    ensure_upgradable(skill)?;
+    let dest = ctx.install_root.join(&skill.install_name);
+    if !dest.exists() {
+        bail!(
+            "installed dir missing for '{}'. Run 'sk doctor --apply' to rebuild first.",
+            skill.install_name
+        );
+    }
+
+    let spec = skill.source.repo_spec_owned();
+    let cache_dir =
+        paths::resolve_or_primary_cache_path(&spec.url, &spec.host, &spec.owner, &spec.repo);
+    git::ensure_cached_repo(&cache_dir, &spec)?;
+    let new_commit = git::resolve_tracked_tip(&cache_dir, &spec, &skill.tracking())?.commit;
+    if new_commit == skill.commit {
+        println!("'{}' is already at the tracked commit.", skill.install_name);
+        return Ok(());
+    }
+    if !git::has_object(&cache_dir, &skill.commit)? {
+        bail!(
+            "locked commit {} missing from cache; cannot use it as the merge base",
+            short_sha(&skill.commit)
+        );
+    }
+
+    let scratch = tempdir().context("create temporary directory for merge")?;
+    let base_dir = scratch.path().join("base");
+    let theirs_dir = scratch.path().join("theirs");
+    let skill_path = skill.source.skill_path();
+    install::extract_subdir_from_commit(&cache_dir, &skill.commit, skill_path, &base_dir)?;
+    install::extract_subdir_from_commit(&cache_dir, &new_commit, skill_path, &theirs_dir)?;
+    let upstream_digest = digest::digest_dir_versioned(&theirs_dir, lf.digest_version)?;
+    let upstream_files = digest::manifest_dir(&theirs_dir)?;
+
+    fs::create_dir_all(&state_dir)?;
+    let backup = state_dir.join(BACKUP_DIR);
+    copy_dir_all(&dest, &backup)
+        .with_context(|| format!("back up {} before merging", dest.display()))?;
+
+    let conflicts = match merge_trees(&base_dir, &theirs_dir, &dest) {
+        Ok(conflicts) => conflicts,
+        Err(err) => {
+            restore_backup(&backup, &dest);
+            let _ = fs::remove_dir_all(&state_dir);
+            return Err(err);
+        }
+    };
+
+    let state = MergeState {
+        install_name: skill.install_name.clone(),
+        base_commit: skill.commit.clone(),
+        new_commit,
+        upstream_digest,
+        upstream_files,
+        conflicts,
+    };
+    if state.conflicts.is_empty() {
+        finish_merge(ctx, &state, &state_dir)?;
+        println!(
+            "Merged upstream {} -> {} into '{}' without conflicts.",
+            short_sha(&state.base_commit),
+            short_sha(&state.new_commit),
+            state.install_name
+        );
+        return Ok(());
+    }
+
+    fs::write(
+        state_dir.join(STATE_FILE),
+        serde_json::to_string_pretty(&state)?,
+    )?;
+    println!(
+        "Merging upstream {} -> {} into '{}' left {} conflicted file(s):",
+        short_sha(&state.base_commit),
+        short_sha(&state.new_commit),
+        state.install_name,
+        state.conflicts.len()
+    );
+    for conflict in &state.conflicts {
+        println!("- {} ({})", conflict.path, conflict.kind.describe());
+    }
+    bail!(
+        "resolve the conflicts (remove markers and any .upstream copies), then run 'sk upgrade {name} --continue' or 'sk upgrade {name} --abort'",
+        name = state.install_name
+    );
+}
+
+pub fn continue_merge(ctx: &MergeContext) -> Result<()> {
+    let state_dir = state_dir(ctx)?;
+    let state = load_state(&state_dir, ctx.install_name)?;
+    let dest = ctx.install_root.join(&state.install_name);
+    let unresolved: Vec<&Conflict> = state
+        .conflicts
+        .iter()
+        .filter(|c| !is_resolved(&dest, c))
+        .collect();
+    if !unresolved.is_empty() {
+        for conflict in &unresolved {
+            eprintln!("- {} ({})", conflict.path, conflict.kind.describe());
+        }
+        bail!(
+            "{} file(s) in '{}' are still conflicted",
+            unresolved.len(),
+            state.install_name
+        );
+    }
+    finish_merge(ctx, &state, &state_dir)?;
+    println!(
+        "Completed merge of '{}' at {}.",
+        state.install_name,
+        short_sha(&state.new_commit)
+    );
+    Ok(())
+}
+
+pub fn abort_merge(ctx: &MergeContext) -> Result<()> {
+    let state_dir = state_dir(ctx)?;
+    let state = load_state(&state_dir, ctx.install_name)?;
+    let dest = ctx.install_root.join(&state.install_name);
+    let backup = state_dir.join(BACKUP_DIR);
+    if !backup.exists() {
+        bail!("pre-merge backup missing at {}", backup.display());
+    }
+    if dest.exists() {
+        fs::remove_dir_all(&dest).with_context(|| format!("remove {}", dest.display()))?;
+    }
+    copy_dir_all(&backup, &dest)?;
+    fs::remove_dir_all(&state_dir).ok();
+    println!("Aborted merge of '{}'.", state.install_name);
+    Ok(())
+}
+
+fn finish_merge(ctx: &MergeContext, state: &MergeState, state_dir: &Path) -> Result<()> {
+    let updates = vec![LockUpdate {
+        install_name: state.install_name.clone(),
+        commit: state.new_commit.clone(),
+        digest: state.upstream_digest.clone(),
+        files: state.upstream_files.clone(),
+    }];
+    lock::edit_lockfile(ctx.lock_path, |lf| apply_updates_to_lockfile(lf, &updates))?;
+    fs::remove_dir_all(state_dir).ok();
+    Ok(())
+}
+
+fn state_dir(ctx: &MergeContext) -> Result<PathBuf> {
+    Ok(git::git_path(ctx.project_root, "sk-merge")?.join(ctx.install_name))
+}
+
+fn load_state(state_dir: &Path, install_name: &str) -> Result<MergeState> {
+    let path = state_dir.join(STATE_FILE);
+    if !path.exists() {
+        bail!("no merge in progress for '{install_name}'");
+    }
+    let data = fs::read(&path).with_context(|| format!("read {}", path.display()))?;
+    serde_json::from_slice(&data).with_context(|| format!("parse {}", path.display()))
+}
+
+fn restore_backup(backup: &Path, dest: &Path) {
+    let _ = fs::remove_dir_all(dest);
+    let _ = copy_dir_all(backup, dest);
+}
+
+fn is_resolved(dest: &Path, conflict: &Conflict) -> bool {
+    let path = dest.join(&conflict.path);
+    match conflict.kind {
+        ConflictKind::Content => match fs::read(&path) {
+            Ok(data) => !has_conflict_markers(&data),
+            Err(_) => true,
+        },
+        ConflictKind::Sidecar => !sidecar_path(&path).exists(),
+        ConflictKind::DeletedUpstream => true,
+    }
+}
+
+fn has_conflict_markers(data: &[u8]) -> bool {
+    String::from_utf8_lossy(data)
+        .lines()
+        .any(|line| line.starts_with("<<<<<<< ") || line.starts_with(">>>>>>> "))
+}
+
+fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(UPSTREAM_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// Apply upstream changes between `base` and `theirs` onto `ours` in place,
+/// file by file, returning whatever could not be merged cleanly.
+fn merge_trees(base: &Path, theirs: &Path, ours: &Path) -> Result<Vec<Conflict>> {
+    let mut all: BTreeSet<PathBuf> = BTreeSet::new();
+    for dir in [base, theirs, ours] {
+        all.extend(digest::list_files(dir));
+    }
+    let mut conflicts = Vec::new();
+    for rel in all {
+        let base_bytes = read_optional(&base.join(&rel))?;
+        let theirs_bytes = read_optional(&theirs.join(&rel))?;
+        let ours_path = ours.join(&rel);
+        let ours_bytes = read_optional(&ours_path)?;
+        if theirs_bytes == base_bytes || theirs_bytes == ours_bytes {
+            continue;
+        }
+        if ours_bytes == base_bytes {
+            match &theirs_bytes {
+                Some(data) => write_file(&ours_path, data)?,
+                None => fs::remove_file(&ours_path)
+                    .with_context(|| format!("remove {}", ours_path.display()))?,
+            }
+            continue;
+        }
+        let kind = match (&base_bytes, &theirs_bytes, &ours_bytes) {
+            (_, None, Some(_)) => ConflictKind::DeletedUpstream,
+            (base_data, Some(theirs_data), Some(ours_data))
+                if is_text(ours_data)
+                    && is_text(theirs_data)
+                    && base_data.as_deref().map(is_text).unwrap_or(true) =>
+            {
+                let base_data = base_data.as_deref().unwrap_or_default();
+                if merge_file(&ours_path, base_data, theirs_data)? {
+                    ConflictKind::Content
+                } else {
+                    continue;
+                }
+            }
+            (_, Some(theirs_data), _) => {
+                write_file(&sidecar_path(&ours_path), theirs_data)?;
+                ConflictKind::Sidecar
+            }
+            (_, None, None) => continue,
+        };
+        conflicts.push(Conflict {
+            path: rel.to_string_lossy().replace('\\', "/"),
+            kind,
+        });
+    }
+    Ok(conflicts)
+}
+
+/// Run `git merge-file` on `ours` in place. Returns true if conflicts remain.
+fn merge_file(ours: &Path, base: &[u8], theirs: &[u8]) -> Result<bool> {
+    let scratch = tempdir().context("create temporary directory for merge-file")?;
+    let base_path = scratch.path().join("base");
+    let theirs_path = scratch.path().join("theirs");
+    fs::write(&base_path, base)?;
+    fs::write(&theirs_path, theirs)?;
+    let out = Command::new("git")
+        .args(["merge-file", "-L", "local", "-L", "base", "-L", "upstream"])
+        .arg(ours)
+        .arg(&base_path)
+        .arg(&theirs_path)
+        .output()
+        .context("git merge-file failed to run")?;
+    // A positive exit status is the number of conflicts (capped at 127);
+    // errors come back as negative values, which the shell reports as 255.
+    match out.status.code() {
+        Some(0) => Ok(false),
+        Some(1..=127) => Ok(true),
+        _ => bail!(
+            "git merge-file failed for {}: {}",
+            ours.display(),
+            String::from_utf8_lossy(&out.stderr).trim()
+        ),
+    }
+}
+
+fn read_optional(path: &Path) -> Result<Option<Vec<u8>>> {
+    if path.is_file() {
+        Ok(Some(
+            fs::read(path).with_context(|| format!("read {}", path.display()))?,
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+fn write_file(path: &Path, data: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, data).with_context(|| format!("write {}", path.display()))
+}
+
+fn is_text(data: &[u8]) -> bool {
+    !digest::looks_binary(data)
+}
+
+fn short_sha(full: &str) -> &str {
+    &full[..full.len().min(7)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_file_reports_conflicts_and_errors_separately() {
+        let dir = tempdir().unwrap();
+        let ours = dir.path().join("notes.md");
+        fs::write(&ours, "local\n").unwrap();
+        assert!(merge_file(&ours, b"base\n", b"upstream\n").unwrap());
+
+        let clean = dir.path().join("clean.md");
+        fs::write(&clean, "base\n").unwrap();
+        assert!(!merge_file(&clean, b"base\n", b"upstream\n").unwrap());
+        assert_eq!(fs::read_to_string(&clean).unwrap(), "upstream\n");
+
+        let err = merge_file(&dir.path().join("missing.md"), b"a\n", b"b\n").unwrap_err();
+        assert!(
+            err.to_string().contains("git merge-file failed"),
+            "unexpected error: {err:#}"
+        );
+    }
+}
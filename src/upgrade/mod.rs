@@ -1,5 +1,6 @@
 mod apply;
 mod fsops;
+mod merge;
 mod plan;
 
 use crate::{config, git, lock, paths};
@@ -12,6 +13,9 @@ pub struct UpgradeArgs<'a> {
     pub target: &'a str, // installed name or "--all"
     pub root: Option<&'a str>,
     pub dry_run: bool,
+    pub merge: bool,
+    pub continue_merge: bool,
+    pub abort_merge: bool,
 }
 
 pub fn run_upgrade(args: UpgradeArgs) -> Result<()> {
@@ -24,6 +28,24 @@ pub fn run_upgrade(args: UpgradeArgs) -> Result<()> {
     if !lock_path.exists() {
         bail!("no lockfile found");
     }
+    if args.merge || args.continue_merge || args.abort_merge {
+        if args.target == "--all" {
+            bail!("--merge, --continue, and --abort take a single installed skill name");
+        }
+        let ctx = merge::MergeContext {
+            project_root: &project_root,
+            install_root: &install_root,
+            lock_path: &lock_path,
+            install_name: args.target,
+        };
+        return if args.continue_merge {
+            merge::continue_merge(&ctx)
+        } else if args.abort_merge {
+            merge::abort_merge(&ctx)
+        } else {
+            merge::run_merge(&ctx)
+        };
+    }
     let lf = lock::Lockfile::load(&lock_path)?;
 
     let targets = resolve_targets(&lf, &args)?;
@@ -32,7 +54,7 @@ pub fn run_upgrade(args: UpgradeArgs) -> Result<()> {
         tasks: plan,
         skipped: skipped_modified,
         refreshes,
-    } = build_upgrade_plan(&targets, &install_root, upgrading_all)?;
+    } = build_upgrade_plan(&targets, &install_root, upgrading_all, lf.digest_version)?;
 
     if args.dry_run {
         for task in &plan {
@@ -68,16 +90,15 @@ pub fn run_upgrade(args: UpgradeArgs) -> Result<()> {
     let mut updates = Vec::new();
     if !plan.is_empty() {
         let staging = TempDir::new_in(&project_root).context("create staging dir")?;
-        let staged = stage_upgrades(staging.path(), &plan)?;
+        let staged = stage_upgrades(staging.path(), &plan, lf.digest_version)?;
         updates = apply_staged_upgrades(&staged)?;
     }
 
-    updates.extend(refreshes.iter().map(|refresh| {
-        (
-            refresh.install_name.clone(),
-            refresh.new_commit.clone(),
-            refresh.new_digest.clone(),
-        )
+    updates.extend(refreshes.iter().map(|refresh| plan::LockUpdate {
+        install_name: refresh.install_name.clone(),
+        commit: refresh.new_commit.clone(),
+        digest: refresh.new_digest.clone(),
+        files: refresh.new_files.clone(),
     }));
     lock::edit_lockfile(&lock_path, |lf| {
         apply_updates_to_lockfile(lf, &updates)?;
@@ -2,7 +2,6 @@ use super::UpgradeArgs;
 use crate::{digest, git, install, lock, paths};
 use anyhow::{bail, Context, Result};
 use std::path::{Path, PathBuf};
-use std::process::Command;
 use tempfile::tempdir;
 
 #[derive(Clone)]
@@ -18,11 +17,21 @@ pub struct StagedUpgrade {
     pub task: UpgradeTask,
     pub staged_path: std::path::PathBuf,
     pub new_digest: String,
+    pub new_files: Vec<digest::FileEntry>,
 }
 pub struct RefreshTarget {
     pub install_name: String,
     pub new_commit: String,
     pub new_digest: String,
+    pub new_files: Vec<digest::FileEntry>,
+}
+
+/// New lock state for one skill once its files are in place.
+pub struct LockUpdate {
+    pub install_name: String,
+    pub commit: String,
+    pub digest: String,
+    pub files: Vec<digest::FileEntry>,
 }
 
 enum UpgradeDecision {
@@ -44,6 +53,7 @@ pub struct SkippedUpgrade {
     pub dest: PathBuf,
     pub cache_dir: PathBuf,
     pub skill_path: String,
+    pub local_changes: Option<digest::ManifestDiff>,
 }
 
 pub struct UpgradePlanResult {
@@ -54,7 +64,13 @@ pub struct UpgradePlanResult {
 
 pub fn resolve_targets(lf: &lock::Lockfile, args: &UpgradeArgs) -> Result<Vec<lock::LockSkill>> {
     if args.target == "--all" {
-        Ok(lf.skills.clone())
+        // Local imports have no upstream to upgrade from.
+        Ok(lf
+            .skills
+            .iter()
+            .filter(|s| !s.source.is_local())
+            .cloned()
+            .collect())
     } else {
         let matches: Vec<lock::LockSkill> = lf
             .skills
@@ -65,20 +81,34 @@ pub fn resolve_targets(lf: &lock::Lockfile, args: &UpgradeArgs) -> Result<Vec<lo
         if matches.is_empty() {
             bail!("skill not found: {}", args.target);
         }
+        ensure_upgradable(&matches[0])?;
         Ok(matches)
     }
 }
 
+pub fn ensure_upgradable(skill: &lock::LockSkill) -> Result<()> {
+    if let Some(path) = skill.source.local_path() {
+        bail!(
+            "'{name}' was installed from {path}, which has no upstream to upgrade from. Run 'sk remove {name}' and 'sk install {path}' to pick up changes.",
+            name = skill.install_name
+        );
+    }
+    Ok(())
+}
+
 pub fn build_upgrade_plan(
     targets: &[lock::LockSkill],
     install_root: &Path,
     allow_skip_dirty: bool,
+    digest_version: u32,
 ) -> Result<UpgradePlanResult> {
     let mut plan = Vec::new();
     let mut skipped = Vec::new();
     let mut refreshes = Vec::new();
     for skill in targets {
-        if let Some(decision) = evaluate_skill_upgrade(skill, install_root, allow_skip_dirty)? {
+        if let Some(decision) =
+            evaluate_skill_upgrade(skill, install_root, allow_skip_dirty, digest_version)?
+        {
             match decision {
                 UpgradeDecision::Plan(task) => plan.push(task),
                 UpgradeDecision::Refresh(refresh) => refreshes.push(refresh),
@@ -97,6 +127,7 @@ fn evaluate_skill_upgrade(
     skill: &lock::LockSkill,
     install_root: &Path,
     allow_skip_dirty: bool,
+    digest_version: u32,
 ) -> Result<Option<UpgradeDecision>> {
     let dest = install_root.join(&skill.install_name);
     if !dest.exists() {
@@ -105,7 +136,7 @@ fn evaluate_skill_upgrade(
             skill.install_name
         );
     }
-    let cur_digest = digest::digest_dir(&dest).ok();
+    let cur_digest = digest::digest_dir_versioned(&dest, digest_version).ok();
     let is_modified = match &cur_digest {
         Some(hash) => hash != &skill.digest,
         None => true,
@@ -114,8 +145,7 @@ fn evaluate_skill_upgrade(
     let cache_dir =
         paths::resolve_or_primary_cache_path(&spec.url, &spec.host, &spec.owner, &spec.repo);
     git::ensure_cached_repo(&cache_dir, &spec)?;
-    let default = git::detect_or_set_default_branch(&cache_dir, &spec)?;
-    let new_commit = git::rev_parse(&cache_dir, &format!("refs/remotes/origin/{default}"))?;
+    let new_commit = git::resolve_tracked_tip(&cache_dir, &spec, &skill.tracking())?.commit;
     let needs_upgrade = new_commit != skill.commit;
 
     if is_modified {
@@ -126,6 +156,7 @@ fn evaluate_skill_upgrade(
                 skill.source.skill_path(),
                 &new_commit,
                 cur_digest.as_deref(),
+                digest_version,
             )?
         } else {
             None
@@ -135,6 +166,7 @@ fn evaluate_skill_upgrade(
                 install_name: skill.install_name.clone(),
                 new_commit: new_commit.clone(),
                 new_digest,
+                new_files: digest::manifest_dir(&dest)?,
             })));
         }
         if allow_skip_dirty {
@@ -145,13 +177,14 @@ fn evaluate_skill_upgrade(
             return Ok(Some(UpgradeDecision::Skip(SkippedUpgrade {
                 install_name: skill.install_name.clone(),
                 span,
+                local_changes: skill.manifest_changes(&dest),
                 dest,
                 cache_dir,
                 skill_path: skill.source.skill_path().to_string(),
             })));
         }
         bail!(
-            "Local edits detected. Refusing to upgrade. Run 'sk sync-back <name>' or revert changes."
+            "Local edits detected. Refusing to upgrade. Run 'sk upgrade <name> --merge', 'sk sync-back <name>', or revert changes."
         );
     }
 
@@ -167,48 +200,26 @@ fn evaluate_skill_upgrade(
     Ok(None)
 }
 
+/// Local edits that exactly match the new upstream tree only need the lock
+/// refreshed. Compares per-file manifests so `.skignore`d files don't count.
 fn detect_zero_diff(
     dest: &Path,
     cache_dir: &Path,
     skill_path: &str,
     new_commit: &str,
     current_digest: Option<&str>,
+    digest_version: u32,
 ) -> Result<Option<String>> {
     let checkout = tempdir().context("create temporary directory for refresh comparison")?;
     install::extract_subdir_from_commit(cache_dir, new_commit, skill_path, checkout.path())
         .with_context(|| format!("extracting '{}' from {}", skill_path, &new_commit[..7]))?;
-    let output = Command::new("git")
-        .arg("--no-pager")
-        .arg("-c")
-        .arg("core.autocrlf=false")
-        .arg("diff")
-        .arg("--no-index")
-        .arg("--src-prefix=local/")
-        .arg("--dst-prefix=remote/")
-        .arg("--")
-        .arg(dest)
-        .arg(checkout.path())
-        .output()
-        .context("git diff --no-index failed to run")?;
-    let no_diff = match output.status.code() {
-        Some(0) => true,
-        Some(1) => {
-            let text = String::from_utf8_lossy(&output.stdout);
-            text.trim().is_empty()
-        }
-        Some(code) => {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            bail!("git diff exited with status {code}: {stderr}")
-        }
-        None => bail!("git diff terminated by signal"),
-    };
-    if !no_diff {
+    if digest::manifest_dir(dest)? != digest::manifest_dir(checkout.path())? {
         return Ok(None);
     }
     let new_digest = if let Some(hash) = current_digest {
         hash.to_string()
     } else {
-        digest::digest_dir(checkout.path())?
+        digest::digest_dir_versioned(checkout.path(), digest_version)?
     };
     Ok(Some(new_digest))
 }
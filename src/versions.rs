@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use semver::{Version, VersionReq};
+
+/// A release tag whose name parsed as a semantic version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaggedVersion {
+    pub tag: String,
+    pub version: Version,
+}
+
+pub fn parse_constraint(raw: &str) -> Result<VersionReq> {
+    VersionReq::parse(raw.trim()).with_context(|| format!("invalid version constraint '{raw}'"))
+}
+
+/// Pick the highest tag satisfying `req` for the skill at `skill_path`.
+///
+/// Skill-scoped tags (`<skill-dir>/v2.3.0` or `<skill_path>/v2.3.0`) take
+/// precedence; repo-wide tags (`v2.3.0`) are only considered when the repo has
+/// no tags scoped to this skill.
+pub fn select_tag(tags: &[String], req: &VersionReq, skill_path: &str) -> Option<TaggedVersion> {
+    let prefixes = scoped_prefixes(skill_path);
+    let scoped: Vec<TaggedVersion> = tags
+        .iter()
+        .filter_map(|tag| {
+            prefixes
+                .iter()
+                .find_map(|prefix| tag.strip_prefix(prefix.as_str()))
+                .and_then(parse_version)
+                .map(|version| TaggedVersion {
+                    tag: tag.clone(),
+                    version,
+                })
+        })
+        .collect();
+    let candidates = if scoped.is_empty() {
+        tags.iter()
+            .filter_map(|tag| {
+                parse_version(tag).map(|version| TaggedVersion {
+                    tag: tag.clone(),
+                    version,
+                })
+            })
+            .collect()
+    } else {
+        scoped
+    };
+    candidates
+        .into_iter()
+        .filter(|candidate| req.matches(&candidate.version))
+        .max_by(|a, b| a.version.cmp(&b.version))
+}
+
+fn scoped_prefixes(skill_path: &str) -> Vec<String> {
+    let trimmed = skill_path.trim().trim_matches('/');
+    if trimmed.is_empty() || trimmed == "." {
+        return Vec::new();
+    }
+    let mut prefixes = vec![format!("{trimmed}/")];
+    if let Some((_, leaf)) = trimmed.rsplit_once('/') {
+        prefixes.push(format!("{leaf}/"));
+    }
+    prefixes
+}
+
+fn parse_version(raw: &str) -> Option<Version> {
+    let stripped = raw.strip_prefix('v').unwrap_or(raw);
+    Version::parse(stripped).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn picks_highest_scoped_tag_matching_constraint() {
+        let all = tags(&[
+            "brand-guidelines/v2.3.0",
+            "brand-guidelines/v2.3.4",
+            "brand-guidelines/v3.0.0",
+            "canvas/v2.9.0",
+            "v2.5.0",
+        ]);
+        let req = parse_constraint("^2.3").unwrap();
+        let picked = select_tag(&all, &req, "skills/brand-guidelines").unwrap();
+        assert_eq!(picked.tag, "brand-guidelines/v2.3.4");
+    }
+
+    #[test]
+    fn falls_back_to_repo_wide_tags() {
+        let all = tags(&["v1.0.0", "v1.2.0", "2.0.0", "not-a-version"]);
+        let req = parse_constraint("~1").unwrap();
+        let picked = select_tag(&all, &req, "demo").unwrap();
+        assert_eq!(picked.tag, "v1.2.0");
+    }
+
+    #[test]
+    fn ignores_prereleases_unless_requested() {
+        let all = tags(&["demo/v1.0.0", "demo/v1.1.0-rc.1"]);
+        let req = parse_constraint("^1").unwrap();
+        assert_eq!(select_tag(&all, &req, "demo").unwrap().tag, "demo/v1.0.0");
+        let none = parse_constraint("^4").unwrap();
+        assert!(select_tag(&all, &none, "demo").is_none());
+    }
+}
@@ -0,0 +1,156 @@
+use std::fs;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::CliFixture;
+
+fn config_get(fx: &CliFixture, args: &[&str]) -> String {
+    let out = fx.sk_cmd().args(args).output().unwrap();
+    assert!(
+        out.status.success(),
+        "config get failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+}
+
+#[test]
+fn project_config_overrides_user_config_and_reports_origin() {
+    let fx = CliFixture::new();
+    let shown = config_get(&fx, &["config", "get", "protocol", "--show-origin"]);
+    assert_eq!(shown, "default\tssh");
+
+    fx.sk_success(&["config", "set", "default_repo", "@me/personal"]);
+    fs::write(
+        fx.project.join(".sk.toml"),
+        "default_root = \"./vendor/skills\"\ndefault_repo = \"@acme/skills\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        config_get(&fx, &["config", "get", "default_repo"]),
+        "@acme/skills"
+    );
+    let shown = config_get(&fx, &["config", "get", "default_repo", "--show-origin"]);
+    assert!(shown.starts_with("project:"), "{shown}");
+    assert!(shown.ends_with(".sk.toml\t@acme/skills"), "{shown}");
+
+    fs::remove_file(fx.project.join(".sk.toml")).unwrap();
+    let shown = config_get(&fx, &["config", "get", "default_repo", "--show-origin"]);
+    assert!(shown.starts_with("user:"), "{shown}");
+    assert!(shown.ends_with("\t@me/personal"), "{shown}");
+}
+
+#[test]
+fn project_default_root_drives_install_location() {
+    let fx = CliFixture::new();
+    fx.sk_success(&[
+        "config",
+        "set",
+        "--project",
+        "default_root",
+        "./vendor/skills",
+    ]);
+    let written = fs::read_to_string(fx.project.join(".sk.toml")).unwrap();
+    assert!(
+        written.contains("default_root = \"./vendor/skills\""),
+        "{written}"
+    );
+
+    fx.sk_success(&["init"]);
+    assert!(fx.project.join("vendor/skills").is_dir());
+
+    // The user config keeps its own value.
+    let user: serde_json::Value =
+        serde_json::from_slice(&fs::read(fx.config_dir().join("config.json")).unwrap()).unwrap();
+    assert_eq!(user["default_root"], "./skills");
+}
+
+#[test]
+fn project_config_rejects_non_string_values() {
+    let fx = CliFixture::new();
+    fs::write(fx.project.join(".sk.toml"), "default_root = 3\n").unwrap();
+    let out = fx
+        .sk_cmd()
+        .args(["config", "get", "default_root"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("must be a string"));
+}
+
+#[test]
+fn env_overrides_win_and_config_list_reports_every_key() {
+    let fx = CliFixture::new();
+    fs::write(
+        fx.project.join(".sk.toml"),
+        "default_repo = \"@acme/skills\"\n",
+    )
+    .unwrap();
+
+    let out = fx
+        .sk_cmd()
+        .env("SK_DEFAULT_REPO", "@ci/skills")
+        .args(["config", "list", "--json"])
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "config list failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let rows: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let rows = rows.as_array().unwrap();
+    assert_eq!(rows.len(), 7);
+    let repo = rows.iter().find(|r| r["key"] == "default_repo").unwrap();
+    assert_eq!(repo["value"], "@ci/skills");
+    assert_eq!(repo["origin"], "env:SK_DEFAULT_REPO");
+    let protocol = rows.iter().find(|r| r["key"] == "protocol").unwrap();
+    assert_eq!(protocol["origin"], "default");
+
+    let table = config_get(&fx, &["config", "list"]);
+    assert!(table.starts_with("KEY"), "{table}");
+    assert!(table.contains("project:"), "{table}");
+}
+
+#[test]
+fn unknown_keys_and_invalid_values_are_rejected() {
+    let fx = CliFixture::new();
+
+    let out = fx
+        .sk_cmd()
+        .args(["config", "set", "protocol", "ftp"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("'ssh' or 'https'"));
+
+    let out = fx
+        .sk_cmd()
+        .args(["config", "set", "default_repo", "@just-an-owner"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+
+    fs::write(
+        fx.config_dir().join("config.json"),
+        "{\"protcol\": \"https\"}",
+    )
+    .unwrap();
+    let out = fx.sk_cmd().args(["config", "list"]).output().unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("unknown config key 'protcol'"), "{stderr}");
+
+    fs::write(fx.config_dir().join("config.json"), "{}").unwrap();
+    let out = fx
+        .sk_cmd()
+        .env("SK_PROTOCOL", "git")
+        .args(["config", "get", "protocol"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("env:SK_PROTOCOL"), "{stderr}");
+}
@@ -0,0 +1,42 @@
+#[path = "support/mod.rs"]
+mod support;
+
+use support::CliFixture;
+
+#[test]
+fn install_and_where_suggest_close_names() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("brand-repo", "skills/brand-guidelines", "brand-guidelines");
+
+    let out = fx
+        .sk_cmd()
+        .args(["install", &remote.file_url(), "brand-guideline"])
+        .output()
+        .unwrap();
+    assert!(
+        !out.status.success(),
+        "install of a misspelled name should fail"
+    );
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("Did you mean 'brand-guidelines'?"),
+        "install should suggest the close name, got: {stderr}"
+    );
+
+    fx.install_from_remote(&remote, "brand-guidelines");
+    let out = fx
+        .sk_cmd()
+        .args(["where", "brand_guideline"])
+        .output()
+        .unwrap();
+    assert!(
+        !out.status.success(),
+        "where should fail for an unknown name"
+    );
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("not found: brand_guideline. Did you mean 'brand-guidelines'?"),
+        "where should suggest installed skills, got: {stderr}"
+    );
+}
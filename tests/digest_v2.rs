@@ -0,0 +1,80 @@
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::{symlink, PermissionsExt};
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::{git, parse_status_entries, CliFixture, RemoteRepo};
+
+fn add_script_and_link(remote: &RemoteRepo) {
+    let skill = remote.work.join(remote.skill_path());
+    let scripts = skill.join("scripts");
+    fs::create_dir_all(&scripts).unwrap();
+    let script = scripts.join("run.sh");
+    fs::write(&script, "#!/bin/sh\necho hi\n").unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    symlink("scripts/run.sh", skill.join("run")).unwrap();
+    git(&["add", "."], &remote.work);
+    git(&["commit", "-m", "add script"], &remote.work);
+    git(&["push", "origin", "main"], &remote.work);
+}
+
+fn install_and_migrate(fx: &CliFixture, repo: &str) -> std::path::PathBuf {
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote(repo, "skills/demo", "demo");
+    add_script_and_link(&remote);
+    fx.install_from_remote(&remote, "demo");
+    fx.sk_success(&["doctor", "--migrate-digests"]);
+    fx.skill_dir("demo")
+}
+
+fn status_state(fx: &CliFixture) -> String {
+    let status = parse_status_entries(fx.run_json(&["doctor", "--status", "--json"]));
+    status[0].state.clone()
+}
+
+#[test]
+fn legacy_lockfiles_ignore_modes_until_migrated() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("digest-legacy", "skills/demo", "demo");
+    add_script_and_link(&remote);
+    fx.install_from_remote(&remote, "demo");
+    assert_eq!(fx.lock_json()["digestVersion"], 1);
+
+    let script = fx.skill_dir("demo").join("scripts/run.sh");
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+    assert_eq!(status_state(&fx), "clean");
+
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    fx.sk_success(&["doctor", "--migrate-digests"]);
+    assert_eq!(fx.lock_json()["digestVersion"], 2);
+    assert_eq!(status_state(&fx), "clean");
+}
+
+#[test]
+fn dropping_the_executable_bit_counts_as_a_modification() {
+    let fx = CliFixture::new();
+    let dir = install_and_migrate(&fx, "digest-mode");
+    let script = dir.join("scripts/run.sh");
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+    assert_eq!(status_state(&fx), "modified");
+
+    let report = fx.sk_cmd().args(["doctor", "demo"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&report.stdout);
+    assert!(stdout.contains("modified: scripts/run.sh"), "{stdout}");
+}
+
+#[test]
+fn replacing_a_symlink_with_a_copy_counts_as_a_modification() {
+    let fx = CliFixture::new();
+    let dir = install_and_migrate(&fx, "digest-link");
+    assert_eq!(status_state(&fx), "clean");
+
+    let link = dir.join("run");
+    fs::remove_file(&link).unwrap();
+    fs::copy(dir.join("scripts/run.sh"), &link).unwrap();
+    assert_eq!(status_state(&fx), "modified");
+}
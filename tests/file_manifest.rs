@@ -0,0 +1,76 @@
+use std::fs;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::CliFixture;
+
+fn installed_with_edits() -> CliFixture {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("manifest", "skills/demo", "demo");
+    remote.overwrite_file("extra.md", "extra\n", "add extra");
+    fx.install_from_remote(&remote, "demo");
+
+    let dir = fx.skill_dir("demo");
+    fs::write(dir.join("file.txt"), "edited\n").unwrap();
+    fs::write(dir.join("new.md"), "new\n").unwrap();
+    fs::remove_file(dir.join("extra.md")).unwrap();
+    fx
+}
+
+#[test]
+fn install_records_per_file_manifest() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("manifest-lock", "skills/demo", "demo");
+    fx.install_from_remote(&remote, "demo");
+
+    let lock = fx.lock_json();
+    let files = lock["skills"][0]["files"].as_array().unwrap();
+    let paths: Vec<&str> = files.iter().map(|f| f["path"].as_str().unwrap()).collect();
+    assert_eq!(paths, vec!["SKILL.md", "file.txt"]);
+    for file in files {
+        assert!(file["hash"].as_str().unwrap().starts_with("sha256:"));
+        assert_eq!(file["mode"].as_str(), Some("100644"));
+    }
+}
+
+#[test]
+fn doctor_lists_changed_files() {
+    let fx = installed_with_edits();
+    let out = fx.sk_cmd().arg("doctor").output().unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Digest mismatch (modified)"), "{stdout}");
+    assert!(stdout.contains("modified: file.txt"), "{stdout}");
+    assert!(stdout.contains("added: new.md"), "{stdout}");
+    assert!(stdout.contains("removed: extra.md"), "{stdout}");
+}
+
+#[test]
+fn remove_refusal_lists_changed_files() {
+    let fx = installed_with_edits();
+    let out = fx.sk_cmd().args(["remove", "demo"]).output().unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("modified: file.txt"), "{stderr}");
+    assert!(stderr.contains("added: new.md"), "{stderr}");
+    assert!(stderr.contains("removed: extra.md"), "{stderr}");
+}
+
+#[test]
+fn precommit_warns_about_unlocked_edits() {
+    let fx = installed_with_edits();
+    let out = fx
+        .sk_cmd()
+        .args(["precommit", "--allow-local"])
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "precommit should only warn");
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("installed skills differ from skills.lock.json"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("modified: file.txt"), "{stderr}");
+}
@@ -0,0 +1,111 @@
+use std::fs;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::{CliFixture, RemoteRepo};
+
+fn catalog_remote(fx: &CliFixture, repo: &str) -> RemoteRepo {
+    let remote = fx.create_remote(repo, "skills/doc-writer", "doc-writer");
+    remote.overwrite_file(
+        "../doc-review/SKILL.md",
+        "---\nname: doc-review\ndescription: review docs\n---\n",
+        "add doc-review",
+    );
+    remote.overwrite_file(
+        "../testing/SKILL.md",
+        "---\nname: testing\ndescription: run tests\n---\n",
+        "add testing",
+    );
+    remote
+}
+
+fn lock_names(fx: &CliFixture) -> Vec<String> {
+    let lock_path = fx.project.join("skills.lock.json");
+    if !lock_path.exists() {
+        return Vec::new();
+    }
+    let mut names: Vec<String> = fx.lock_json()["skills"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["installName"].as_str().unwrap().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn install_all_installs_every_skill_in_the_repo() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = catalog_remote(&fx, "batch-all");
+
+    let url = remote.file_url();
+    let out = fx
+        .sk_cmd()
+        .args(["install", &url, "--all"])
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "install --all failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    for name in ["doc-review", "doc-writer", "testing"] {
+        assert!(
+            stdout.contains(&format!("Installed '{name}'")),
+            "missing report for {name}: {stdout}"
+        );
+        assert!(fx.skill_dir(name).join("SKILL.md").exists());
+    }
+    assert!(stdout.contains("Installed 3 skill(s)"), "{stdout}");
+    assert_eq!(lock_names(&fx), vec!["doc-review", "doc-writer", "testing"]);
+}
+
+#[test]
+fn install_glob_only_installs_matching_skills() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = catalog_remote(&fx, "batch-glob");
+
+    let url = remote.file_url();
+    fx.sk_success(&["install", &url, "doc-*"]);
+    assert_eq!(lock_names(&fx), vec!["doc-review", "doc-writer"]);
+    assert!(!fx.skill_dir("testing").exists());
+
+    let out = fx
+        .sk_cmd()
+        .args(["install", &url, "nothing-*"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("No skills matching 'nothing-*'"));
+}
+
+#[test]
+fn batch_install_applies_nothing_when_any_skill_fails() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = catalog_remote(&fx, "batch-atomic");
+    let blocker = fx.skill_dir("doc-review");
+    fs::create_dir_all(&blocker).unwrap();
+    fs::write(blocker.join("notes.txt"), "hand-written\n").unwrap();
+
+    let url = remote.file_url();
+    let out = fx
+        .sk_cmd()
+        .args(["install", &url, "--all"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("doc-review"), "{stderr}");
+    assert!(stderr.contains("nothing was installed"), "{stderr}");
+
+    assert!(lock_names(&fx).is_empty());
+    assert!(!fx.skill_dir("doc-writer").exists());
+    assert!(!fx.skill_dir("testing").exists());
+    assert!(!blocker.join("SKILL.md").exists());
+}
@@ -0,0 +1,118 @@
+use std::fs;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::{git, parse_status_entries, CliFixture};
+
+fn tag_and_push(remote: &support::RemoteRepo, tag: &str) {
+    git(&["tag", tag], &remote.work);
+    git(&["push", "origin", tag], &remote.work);
+}
+
+#[test]
+fn constraint_install_tracks_highest_matching_tag() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let remote = fx.create_remote("constraint", "skills/demo", "demo");
+    tag_and_push(&remote, "demo/v2.3.0");
+    remote.overwrite_file("file.txt", "v2.3.4\n", "patch release");
+    let patch = remote.head();
+    tag_and_push(&remote, "demo/v2.3.4");
+    remote.overwrite_file("file.txt", "v3\n", "breaking release");
+    tag_and_push(&remote, "demo/v3.0.0");
+
+    fx.sk_success(&[
+        "install",
+        &remote.file_url(),
+        "demo",
+        "--constraint",
+        "^2.3",
+    ]);
+
+    let lock = fx.lock_json();
+    assert_eq!(lock["version"].as_u64(), Some(2));
+    assert_eq!(lock["skills"][0]["constraint"].as_str(), Some("^2.3"));
+    assert_eq!(lock["skills"][0]["commit"].as_str(), Some(patch.as_str()));
+    assert_eq!(
+        fs::read_to_string(fx.skill_dir("demo").join("file.txt")).unwrap(),
+        "v2.3.4\n"
+    );
+
+    let status = parse_status_entries(fx.run_json(&["doctor", "--status", "--json"]));
+    assert!(
+        status[0].update.is_none(),
+        "3.0.0 is outside ^2.3 and must not be offered: {:?}",
+        status[0]
+    );
+
+    git(&["checkout", &patch], &remote.work);
+    fs::write(remote.work.join("skills/demo/file.txt"), "v2.4.0\n").unwrap();
+    git(&["commit", "-am", "minor release"], &remote.work);
+    let minor = remote.head();
+    tag_and_push(&remote, "demo/v2.4.0");
+    git(&["checkout", "main"], &remote.work);
+
+    fx.sk_success(&["upgrade", "--all"]);
+    let lock = fx.lock_json();
+    assert_eq!(lock["skills"][0]["commit"].as_str(), Some(minor.as_str()));
+    assert_eq!(lock["skills"][0]["constraint"].as_str(), Some("^2.3"));
+    assert_eq!(
+        fs::read_to_string(fx.skill_dir("demo").join("file.txt")).unwrap(),
+        "v2.4.0\n"
+    );
+}
+
+#[test]
+fn version_one_lockfile_with_constraint_is_refused() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let remote = fx.create_remote("constraint-legacy", "skills/demo", "demo");
+    tag_and_push(&remote, "demo/v1.0.0");
+    fx.sk_success(&["install", &remote.file_url(), "demo", "--constraint", "^1"]);
+
+    let mut lock = fx.lock_json();
+    lock["version"] = 1.into();
+    fs::write(
+        fx.project.join("skills.lock.json"),
+        serde_json::to_string_pretty(&lock).unwrap(),
+    )
+    .unwrap();
+
+    let out = fx.sk_cmd().args(["list"]).output().unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("records a \"constraint\" for install 'demo'"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn install_fails_when_no_tag_satisfies_constraint() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let remote = fx.create_remote("constraint-none", "skills/demo", "demo");
+    tag_and_push(&remote, "v1.0.0");
+
+    let out = fx
+        .sk_cmd()
+        .args([
+            "install",
+            &remote.file_url(),
+            "demo",
+            "--path",
+            "skills/demo",
+            "--constraint",
+            ">=2",
+        ])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("satisfies '>=2'"), "stderr: {stderr}");
+    assert!(!fx.skill_dir("demo").exists());
+}
@@ -0,0 +1,122 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::{parse_status_entries, CliFixture};
+
+fn write_skill(dir: &Path, name: &str) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        format!("---\nname: {name}\ndescription: handed over\n---\n# {name}\n"),
+    )
+    .unwrap();
+    fs::write(dir.join("notes.md"), "extra\n").unwrap();
+}
+
+fn write_zip(path: &Path, top: &str, name: &str) {
+    let file = fs::File::create(path).unwrap();
+    let mut zip = zip::ZipWriter::new(file);
+    let opts = zip::write::SimpleFileOptions::default();
+    zip.add_directory(format!("{top}/"), opts).unwrap();
+    zip.start_file(format!("{top}/SKILL.md"), opts).unwrap();
+    zip.write_all(format!("---\nname: {name}\ndescription: exported\n---\n").as_bytes())
+        .unwrap();
+    zip.start_file("__MACOSX/._SKILL.md", opts).unwrap();
+    zip.write_all(b"junk").unwrap();
+    zip.finish().unwrap();
+}
+
+#[test]
+fn install_from_directory_records_local_source() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    write_skill(&fx.project.join("handoff/brand-voice"), "brand-voice");
+
+    fx.sk_success(&["install", "handoff/brand-voice"]);
+
+    let installed = fx.skill_dir("brand-voice");
+    assert!(installed.join("SKILL.md").exists());
+    assert!(installed.join("notes.md").exists());
+    let lock = fx.lock_json();
+    let entry = &lock["skills"][0];
+    assert_eq!(entry["installName"], "brand-voice");
+    assert_eq!(entry["source"]["kind"], "local");
+    assert_eq!(entry["source"]["path"], "handoff/brand-voice");
+    assert_eq!(entry["source"]["skillPath"], ".");
+    assert!(entry.get("commit").is_none());
+
+    let status = parse_status_entries(fx.run_json(&["doctor", "--status", "--json"]));
+    assert_eq!(status[0].state, "clean");
+    assert!(status[0].update.is_none());
+
+    fs::write(installed.join("notes.md"), "edited\n").unwrap();
+    let status = parse_status_entries(fx.run_json(&["doctor", "--status", "--json"]));
+    assert_eq!(status[0].state, "modified");
+
+    // The origin is inside the project, so precommit accepts it.
+    fx.sk_success(&["precommit"]);
+
+    let out = fx.sk_cmd().args(["cache", "refresh"]).output().unwrap();
+    assert!(out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("no git-sourced skills to refresh"),
+        "stdout: {stdout}"
+    );
+}
+
+#[test]
+fn install_from_zip_with_top_level_folder() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let archive = fx.root.join("export.zip");
+    write_zip(&archive, "pdf-tools", "pdf-tools");
+
+    let out = fx
+        .sk_cmd()
+        .args(["install", archive.to_str().unwrap(), "--alias", "pdf"])
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "zip install failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert!(fx.skill_dir("pdf").join("SKILL.md").exists());
+    assert!(!fx.skill_dir("pdf").join("__MACOSX").exists());
+    let lock = fx.lock_json();
+    assert_eq!(lock["skills"][0]["source"]["kind"], "local");
+    assert_eq!(lock["skills"][0]["source"]["skillPath"], "pdf-tools");
+
+    // Outside the project, the origin is machine-specific.
+    let out = fx.sk_cmd().args(["precommit"]).output().unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("pdf ->"));
+    fx.sk_success(&["precommit", "--allow-local"]);
+}
+
+#[test]
+fn doctor_apply_rebuilds_local_install_from_origin() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    write_skill(&fx.project.join("handoff/brand-voice"), "brand-voice");
+    fx.sk_success(&["install", "handoff/brand-voice"]);
+
+    fs::remove_dir_all(fx.skill_dir("brand-voice")).unwrap();
+    let out = fx.sk_cmd().args(["doctor", "--apply"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Rebuilt from local source"), "{stdout}");
+    assert!(fx.skill_dir("brand-voice").join("notes.md").exists());
+
+    let out = fx
+        .sk_cmd()
+        .args(["upgrade", "brand-voice"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("no upstream"));
+}
@@ -0,0 +1,151 @@
+use std::fs;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::{git, parse_status_entries, CliFixture};
+
+fn install_with_ref(fx: &CliFixture, remote: &support::RemoteRepo, name: &str, git_ref: &str) {
+    let file_url = remote.file_url();
+    let out = fx
+        .sk_cmd()
+        .args([
+            "install",
+            &file_url,
+            name,
+            "--path",
+            remote.skill_path(),
+            "--ref",
+            git_ref,
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "install --ref failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+#[test]
+fn tag_pinned_install_ignores_default_branch_updates() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let remote = fx.create_remote("pin-tag", "skills/demo", "demo");
+    let v1 = remote.head();
+    git(&["tag", "v1.4.0"], &remote.work);
+    git(&["push", "origin", "v1.4.0"], &remote.work);
+    remote.overwrite_file("file.txt", "v2\n", "main moves on");
+
+    install_with_ref(&fx, &remote, "demo", "v1.4.0");
+
+    let lock = fx.lock_json();
+    assert_eq!(lock["version"].as_u64(), Some(2));
+    assert_eq!(lock["skills"][0]["ref"].as_str(), Some("v1.4.0"));
+    assert_eq!(lock["skills"][0]["commit"].as_str(), Some(v1.as_str()));
+    assert_eq!(
+        fs::read_to_string(fx.skill_dir("demo").join("file.txt")).unwrap(),
+        "v1\n"
+    );
+
+    let status = parse_status_entries(fx.run_json(&["doctor", "--status", "--json"]));
+    assert!(
+        status[0].update.is_none(),
+        "tag-pinned install should not report main as an upgrade: {:?}",
+        status[0]
+    );
+
+    fx.sk_success(&["upgrade", "--all"]);
+    let lock = fx.lock_json();
+    assert_eq!(lock["skills"][0]["commit"].as_str(), Some(v1.as_str()));
+    assert_eq!(lock["skills"][0]["ref"].as_str(), Some("v1.4.0"));
+}
+
+#[test]
+fn branch_pinned_install_follows_that_branch() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let remote = fx.create_remote("pin-branch", "skills/demo", "demo");
+    git(&["checkout", "-b", "stable"], &remote.work);
+    git(&["push", "-u", "origin", "stable"], &remote.work);
+    git(&["checkout", "main"], &remote.work);
+
+    install_with_ref(&fx, &remote, "demo", "stable");
+
+    remote.overwrite_file("file.txt", "main only\n", "main change");
+    git(&["checkout", "stable"], &remote.work);
+    fs::write(remote.work.join("skills/demo/file.txt"), "stable v2\n").unwrap();
+    git(&["commit", "-am", "stable change"], &remote.work);
+    git(&["push", "origin", "stable"], &remote.work);
+    let stable_tip = remote.head();
+    git(&["checkout", "main"], &remote.work);
+
+    fx.sk_success(&["upgrade", "demo"]);
+    let lock = fx.lock_json();
+    assert_eq!(
+        lock["skills"][0]["commit"].as_str(),
+        Some(stable_tip.as_str())
+    );
+    assert_eq!(
+        fs::read_to_string(fx.skill_dir("demo").join("file.txt")).unwrap(),
+        "stable v2\n"
+    );
+}
+
+#[test]
+fn version_one_lockfile_with_ref_is_refused() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let remote = fx.create_remote("pin-legacy", "skills/demo", "demo");
+    install_with_ref(&fx, &remote, "demo", "main");
+
+    // A pre-tracking lockfile used "ref" for a field sk no longer honors; it
+    // must not be silently reinterpreted as a branch to follow.
+    let mut lock = fx.lock_json();
+    lock["version"] = 1.into();
+    fs::write(
+        fx.project.join("skills.lock.json"),
+        serde_json::to_string_pretty(&lock).unwrap(),
+    )
+    .unwrap();
+
+    let out = fx.sk_cmd().args(["list"]).output().unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("still contains \"ref\" for install 'demo'"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn install_rejects_unknown_ref() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let remote = fx.create_remote("pin-missing", "skills/demo", "demo");
+    let file_url = remote.file_url();
+    let out = fx
+        .sk_cmd()
+        .args([
+            "install",
+            &file_url,
+            "demo",
+            "--path",
+            "skills/demo",
+            "--ref",
+            "does-not-exist",
+        ])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("unable to resolve ref 'does-not-exist'"),
+        "stderr: {stderr}"
+    );
+    assert!(!fx.skill_dir("demo").exists());
+}
@@ -0,0 +1,124 @@
+use std::fs;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::CliFixture;
+
+fn write_skill(fx: &CliFixture, dir_name: &str, skill_md: &str) -> std::path::PathBuf {
+    let dir = fx.skill_dir(dir_name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("SKILL.md"), skill_md).unwrap();
+    dir
+}
+
+#[test]
+fn clean_skill_passes_lint() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let dir = write_skill(
+        &fx,
+        "tidy",
+        "---\nname: tidy\ndescription: keeps things tidy\n---\nRun [the script](scripts/tidy.sh) or see [docs](https://example.com).\n",
+    );
+    fs::create_dir_all(dir.join("scripts")).unwrap();
+    fs::write(dir.join("scripts/tidy.sh"), "#!/bin/sh\n").unwrap();
+
+    let out = fx.sk_cmd().args(["lint"]).output().unwrap();
+    assert!(
+        out.status.success(),
+        "lint failed: {}",
+        String::from_utf8_lossy(&out.stdout)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("Linted 1 skill(s): 0 error(s), 0 warning(s)."));
+}
+
+#[test]
+fn lint_reports_authoring_problems_and_fails() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let dir = write_skill(
+        &fx,
+        "messy",
+        "---\nname: Messy_Skill\ndescription: fine\n---\nSee [missing](refs/guide.md) and [escape](../other/SKILL.md).\n",
+    );
+    fs::write(dir.join("logo.bin"), [0u8, 159, 146, 150]).unwrap();
+    fs::write(dir.join("huge.txt"), "x".repeat(1024 * 1024 + 1)).unwrap();
+    write_skill(
+        &fx,
+        "blank",
+        &format!(
+            "---\nname: blank\ndescription: {}\n---\n\n",
+            "d".repeat(1025)
+        ),
+    );
+
+    let out = fx.sk_cmd().args(["lint", "--json"]).output().unwrap();
+    assert!(!out.status.success(), "lint should fail on errors");
+    let diagnostics: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let found: Vec<(String, String, String)> = diagnostics
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| {
+            (
+                d["skill"].as_str().unwrap().to_string(),
+                d["rule"].as_str().unwrap().to_string(),
+                d["severity"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    let has = |skill: &str, rule: &str, severity: &str| {
+        found
+            .iter()
+            .filter(|(s, r, sev)| s == skill && r == rule && sev == severity)
+            .count()
+    };
+    assert_eq!(has("messy", "name", "error"), 1, "{found:?}");
+    assert_eq!(has("messy", "name-mismatch", "error"), 1, "{found:?}");
+    assert_eq!(has("messy", "broken-link", "error"), 2, "{found:?}");
+    assert_eq!(has("messy", "binary-file", "warning"), 1, "{found:?}");
+    assert_eq!(has("messy", "oversized-file", "error"), 1, "{found:?}");
+    assert_eq!(has("blank", "description", "error"), 1, "{found:?}");
+    assert_eq!(has("blank", "empty-body", "error"), 1, "{found:?}");
+
+    let out = fx.sk_cmd().args(["lint", "blank"]).output().unwrap();
+    assert!(!out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("blank: error[empty-body] SKILL.md:"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("messy"), "{stdout}");
+}
+
+#[test]
+fn aliased_installs_only_warn_about_name_mismatch() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("lint-alias", "skills/demo", "demo");
+    remote.overwrite_file(
+        "SKILL.md",
+        "---\nname: demo\ndescription: fixture\n---\nBody.\n",
+        "add body",
+    );
+    let url = remote.file_url();
+    fx.sk_success(&[
+        "install",
+        &url,
+        "demo",
+        "--path",
+        remote.skill_path(),
+        "--alias",
+        "renamed",
+    ]);
+
+    let out = fx.sk_cmd().args(["lint"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(out.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("renamed: warning[name-mismatch]"),
+        "{stdout}"
+    );
+}
@@ -40,3 +40,30 @@ fn list_prints_name_and_description() {
     assert_eq!(entry["skillPath"], "demo");
     assert_eq!(entry["description"], "fixture");
 }
+
+#[test]
+fn json_listings_include_optional_front_matter() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let remote = fx.create_remote("skills-fm", "demo", "demo-fm");
+    remote.overwrite_file(
+        "SKILL.md",
+        "---\nname: demo-fm\ndescription: fixture\nlicense: Apache-2.0\nallowed-tools: Read, Grep\nversion: 1.2.0\nmetadata:\n  owner: docs\nx-audience: internal\n---\n",
+        "add front-matter",
+    );
+    fx.install_from_remote(&remote, "demo-fm");
+
+    let json = fx.run_json(&["list", "--json"]);
+    let entry = &json.as_array().expect("array output")[0];
+    assert_eq!(entry["license"], "Apache-2.0");
+    assert_eq!(entry["allowed-tools"], serde_json::json!(["Read", "Grep"]));
+    assert_eq!(entry["version"], "1.2.0");
+    assert_eq!(entry["metadata"]["owner"], "docs");
+    assert_eq!(entry["extra"]["x-audience"], "internal");
+
+    let hits = fx.run_json(&["repo", "search", "--all", "--json"]);
+    let hit = &hits.as_array().expect("search json array")[0];
+    assert_eq!(hit["license"], "Apache-2.0");
+    assert_eq!(hit["allowed-tools"], serde_json::json!(["Read", "Grep"]));
+}
@@ -1,6 +1,7 @@
 use std::fs;
 use std::io::{BufRead, BufReader, Write};
 use std::process::{ChildStdin, ChildStdout, Stdio};
+use std::time::Duration;
 
 use regex::Regex;
 use serde_json::{json, Value};
@@ -19,7 +20,7 @@ fn mcp_server_search_and_show_skill() {
     fs::create_dir_all(&landing_skill).unwrap();
     fs::write(
         landing_skill.join("SKILL.md"),
-        "---\nname: landing-the-plane\ndescription: landing checklist\n---\nAlways run the landing checklist before touching down.\n",
+        "---\nname: landing-the-plane\ndescription: landing checklist\nlicense: MIT\n---\nAlways run the landing checklist before touching down.\n",
     )
     .unwrap();
 
@@ -102,6 +103,10 @@ fn mcp_server_search_and_show_skill() {
         .as_str()
         .unwrap();
     assert!(body.contains("landing checklist"));
+    assert_eq!(
+        show_resp["result"]["structuredContent"]["skill"]["license"].as_str(),
+        Some("MIT")
+    );
 
     send_frame(
         &mut stdin,
@@ -201,6 +206,25 @@ fn mcp_server_search_and_show_skill() {
         "skills_list should not include skill bodies"
     );
 
+    send_frame(
+        &mut stdin,
+        json!({
+            "jsonrpc":"2.0",
+            "id":9,
+            "method":"tools/call",
+            "params":{
+                "name":"skills_show",
+                "arguments":{"skillName":"landing-the-plan"}
+            }
+        }),
+    );
+    let typo_resp = expect_response(&mut reader, 9);
+    let message = typo_resp["error"]["message"].as_str().unwrap_or_default();
+    assert!(
+        message.contains("Did you mean 'landing-the-plane'?"),
+        "skills_show should suggest close names, got: {typo_resp}"
+    );
+
     drop(stdin);
     let _ = child.kill();
     let _ = child.wait();
@@ -305,6 +329,290 @@ fn mcp_server_tool_names_are_sanitized() {
     let _ = child.wait();
 }
 
+#[test]
+fn mcp_server_exposes_skills_as_prompts() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let review = fx.skill_dir("code-review");
+    fs::create_dir_all(&review).unwrap();
+    fs::write(
+        review.join("SKILL.md"),
+        "---\nname: code-review\ndescription: Review a change\narguments:\n  - name: focus\n    description: Area to concentrate on\n    required: true\n  - tone\n---\nReview the change, focusing on {{focus}}.{{tone}}\n",
+    )
+    .unwrap();
+
+    let mut child = fx
+        .sk_process()
+        .arg("mcp-server")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("spawn mcp server");
+
+    let mut stdin = child.stdin.take().expect("stdin");
+    let stdout = child.stdout.take().expect("stdout");
+    let mut reader = BufReader::new(stdout);
+
+    send_frame(&mut stdin, initialize_request(1));
+    let init = expect_response(&mut reader, 1);
+    assert_eq!(
+        init["result"]["capabilities"]["prompts"]["listChanged"],
+        json!(true),
+        "server should advertise prompts with listChanged: {init}"
+    );
+    send_frame(
+        &mut stdin,
+        json!({"jsonrpc":"2.0","method":"notifications/initialized","params":{}}),
+    );
+
+    send_frame(
+        &mut stdin,
+        json!({"jsonrpc":"2.0","id":2,"method":"prompts/list","params":{}}),
+    );
+    let list_resp = expect_response(&mut reader, 2);
+    let prompts = list_resp["result"]["prompts"].as_array().unwrap();
+    let prompt = prompts
+        .iter()
+        .find(|p| p["name"] == "code-review")
+        .expect("code-review prompt listed");
+    assert_eq!(prompt["description"], "Review a change");
+    let args = prompt["arguments"].as_array().unwrap();
+    assert_eq!(args.len(), 2);
+    assert_eq!(args[0]["name"], "focus");
+    assert_eq!(args[0]["required"], json!(true));
+    assert_eq!(args[1]["required"], json!(false));
+
+    send_frame(
+        &mut stdin,
+        json!({
+            "jsonrpc":"2.0",
+            "id":3,
+            "method":"prompts/get",
+            "params":{"name":"code-review","arguments":{"focus":"error handling"}}
+        }),
+    );
+    let get_resp = expect_response(&mut reader, 3);
+    let messages = get_resp["result"]["messages"].as_array().unwrap();
+    assert_eq!(messages[0]["role"], "user");
+    assert_eq!(
+        messages[0]["content"]["text"],
+        "Review the change, focusing on error handling."
+    );
+
+    send_frame(
+        &mut stdin,
+        json!({
+            "jsonrpc":"2.0",
+            "id":4,
+            "method":"prompts/get",
+            "params":{"name":"code-review","arguments":{}}
+        }),
+    );
+    let missing = expect_response(&mut reader, 4);
+    let message = missing["error"]["message"].as_str().unwrap_or_default();
+    assert!(
+        message.contains("missing required argument 'focus'"),
+        "expected missing-argument error, got: {missing}"
+    );
+
+    drop(stdin);
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[test]
+fn mcp_server_serves_bundled_skill_files() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let skill = fx.skill_dir("pdf-tools");
+    fs::create_dir_all(skill.join("scripts")).unwrap();
+    fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: pdf-tools\ndescription: PDF helpers\n---\nRun scripts/extract.py.\n",
+    )
+    .unwrap();
+    fs::write(skill.join("scripts/extract.py"), "print('extract')\n").unwrap();
+    fs::write(skill.join("logo.png"), [0x89, b'P', b'N', b'G', 0x00, 0xff]).unwrap();
+    fs::create_dir_all(skill.join("references")).unwrap();
+    fs::write(skill.join("references/style guide.md"), "# Style\n").unwrap();
+
+    let mut child = fx
+        .sk_process()
+        .arg("mcp-server")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("spawn mcp server");
+
+    let mut stdin = child.stdin.take().expect("stdin");
+    let stdout = child.stdout.take().expect("stdout");
+    let mut reader = BufReader::new(stdout);
+
+    send_frame(&mut stdin, initialize_request(1));
+    let _ = expect_response(&mut reader, 1);
+    send_frame(
+        &mut stdin,
+        json!({"jsonrpc":"2.0","method":"notifications/initialized","params":{}}),
+    );
+
+    send_frame(
+        &mut stdin,
+        json!({"jsonrpc":"2.0","id":2,"method":"resources/templates/list","params":{}}),
+    );
+    let templates_resp = expect_response(&mut reader, 2);
+    let templates = templates_resp["result"]["resourceTemplates"]
+        .as_array()
+        .unwrap();
+    assert!(
+        templates
+            .iter()
+            .any(|t| t["uriTemplate"] == "sk://skill/{name}/{path}"),
+        "expected skill file template: {templates_resp}"
+    );
+
+    send_frame(
+        &mut stdin,
+        json!({"jsonrpc":"2.0","id":3,"method":"resources/list","params":{}}),
+    );
+    let list_resp = expect_response(&mut reader, 3);
+    let uris: Vec<&str> = list_resp["result"]["resources"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|r| r["uri"].as_str())
+        .collect();
+    assert!(uris.contains(&"sk://skill/pdf-tools/scripts/extract.py"));
+    assert!(uris.contains(&"sk://skill/pdf-tools/logo.png"));
+    assert!(
+        uris.contains(&"sk://skill/pdf-tools/references/style%20guide.md"),
+        "{uris:?}"
+    );
+
+    send_frame(
+        &mut stdin,
+        json!({
+            "jsonrpc":"2.0",
+            "id":4,
+            "method":"resources/read",
+            "params":{"uri":"sk://skill/pdf-tools/scripts/extract.py"}
+        }),
+    );
+    let script_resp = expect_response(&mut reader, 4);
+    let script = &script_resp["result"]["contents"][0];
+    assert_eq!(script["text"], "print('extract')\n");
+    assert_eq!(script["mimeType"], "text/x-python");
+
+    send_frame(
+        &mut stdin,
+        json!({
+            "jsonrpc":"2.0",
+            "id":5,
+            "method":"resources/read",
+            "params":{"uri":"sk://skill/pdf-tools/logo.png"}
+        }),
+    );
+    let logo_resp = expect_response(&mut reader, 5);
+    let logo = &logo_resp["result"]["contents"][0];
+    assert_eq!(logo["mimeType"], "image/png");
+    assert_eq!(logo["blob"], "iVBORwD/");
+    assert!(logo.get("text").is_none());
+
+    fs::write(skill.parent().unwrap().join("secret.txt"), "outside").unwrap();
+    send_frame(
+        &mut stdin,
+        json!({
+            "jsonrpc":"2.0",
+            "id":6,
+            "method":"resources/read",
+            "params":{"uri":"sk://skill/pdf-tools/../secret.txt"}
+        }),
+    );
+    let escape_resp = expect_response(&mut reader, 6);
+    assert!(
+        escape_resp.get("error").is_some(),
+        "paths outside the skill root must be rejected: {escape_resp}"
+    );
+
+    send_frame(
+        &mut stdin,
+        json!({
+            "jsonrpc":"2.0",
+            "id":7,
+            "method":"resources/read",
+            "params":{"uri":"sk://skill/pdf-tools/references/style%20guide.md"}
+        }),
+    );
+    let spaced_resp = expect_response(&mut reader, 7);
+    let spaced = &spaced_resp["result"]["contents"][0];
+    assert_eq!(spaced["text"], "# Style\n", "{spaced_resp}");
+    assert_eq!(spaced["_meta"]["path"], "references/style guide.md");
+
+    drop(stdin);
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[test]
+fn mcp_server_index_tracks_skill_changes() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let mut child = fx
+        .sk_process()
+        .arg("mcp-server")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("spawn mcp server");
+
+    let mut stdin = child.stdin.take().expect("stdin");
+    let stdout = child.stdout.take().expect("stdout");
+    let mut reader = BufReader::new(stdout);
+
+    send_frame(&mut stdin, initialize_request(1));
+    let _ = expect_response(&mut reader, 1);
+    send_frame(
+        &mut stdin,
+        json!({"jsonrpc":"2.0","method":"notifications/initialized","params":{}}),
+    );
+
+    // Give the watcher a moment to come up so the change arrives as an event.
+    std::thread::sleep(Duration::from_millis(300));
+    let added = fx.skill_dir("late-arrival");
+    fs::create_dir_all(&added).unwrap();
+    fs::write(
+        added.join("SKILL.md"),
+        "---\nname: late-arrival\ndescription: added after startup\n---\nFreshly indexed body.\n",
+    )
+    .unwrap();
+
+    let mut found = false;
+    for id in 2..60 {
+        send_frame(
+            &mut stdin,
+            json!({
+                "jsonrpc":"2.0",
+                "id":id,
+                "method":"tools/call",
+                "params":{"name":"skills_search","arguments":{"query":"freshly indexed"}}
+            }),
+        );
+        let resp = expect_response(&mut reader, id);
+        if resp["result"]["structuredContent"]["total"] == json!(1) {
+            found = true;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    assert!(found, "skill written after startup should reach the index");
+
+    drop(stdin);
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
 fn send_frame(stdin: &mut ChildStdin, payload: Value) {
     serde_json::to_writer(&mut *stdin, &payload).unwrap();
     stdin.write_all(b"\n").unwrap();
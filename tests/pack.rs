@@ -0,0 +1,192 @@
+use std::fs;
+use std::io::Write;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::CliFixture;
+
+fn zip_names(path: &std::path::Path) -> Vec<String> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(path).unwrap()).unwrap();
+    (0..archive.len())
+        .map(|i| archive.by_index(i).unwrap().name().to_string())
+        .collect()
+}
+
+#[test]
+fn pack_is_deterministic_and_honors_skignore() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("pack-src", "skills/demo", "demo");
+    fx.install_from_remote(&remote, "demo");
+    let dir = fx.skill_dir("demo");
+    fs::write(dir.join(".skignore"), "scratch/\n").unwrap();
+    fs::create_dir_all(dir.join("scratch")).unwrap();
+    fs::write(dir.join("scratch/tmp.txt"), "local only\n").unwrap();
+    fs::create_dir_all(dir.join("scripts")).unwrap();
+    fs::write(dir.join("scripts/run.sh"), "#!/bin/sh\n").unwrap();
+
+    let first = fx.root.join("out/first.zip");
+    let second = fx.root.join("out/second.zip");
+    fx.sk_success(&["pack", "demo", "--out", first.to_str().unwrap()]);
+    fx.sk_success(&["pack", "demo", "--out", second.to_str().unwrap()]);
+
+    assert_eq!(fs::read(&first).unwrap(), fs::read(&second).unwrap());
+    let names = zip_names(&first);
+    assert!(names.contains(&"demo/SKILL.md".to_string()), "{names:?}");
+    assert!(names.contains(&"demo/scripts/run.sh".to_string()));
+    assert!(!names.iter().any(|n| n.contains("scratch")), "{names:?}");
+}
+
+#[test]
+fn pack_rejects_invalid_front_matter() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let dir = fx.skill_dir("broken");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("SKILL.md"), "---\nname: broken\n---\nbody\n").unwrap();
+
+    let out = fx.sk_cmd().args(["pack", "broken"]).output().unwrap();
+    assert!(!out.status.success());
+    assert!(!fx.project.join("broken.zip").exists());
+}
+
+#[test]
+fn unpack_round_trips_a_packed_skill() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("unpack-src", "skills/demo", "demo");
+    fx.install_from_remote(&remote, "demo");
+    let archive = fx.project.join("dist/demo.zip");
+    fx.sk_success(&["pack", "demo", "--out", archive.to_str().unwrap()]);
+
+    fx.sk_success(&["unpack", archive.to_str().unwrap(), "--alias", "demo-copy"]);
+
+    let lock = fx.lock_json();
+    let entry = lock["skills"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["installName"] == "demo-copy")
+        .expect("unpacked skill locked")
+        .clone();
+    assert_eq!(entry["source"]["kind"], "local");
+    assert_eq!(entry["source"]["path"], "dist/demo.zip");
+    assert_eq!(entry["source"]["skillPath"], "demo");
+    assert_eq!(
+        fs::read_to_string(fx.skill_dir("demo-copy").join("SKILL.md")).unwrap(),
+        fs::read_to_string(fx.skill_dir("demo").join("SKILL.md")).unwrap()
+    );
+}
+
+#[cfg(unix)]
+fn write_escaping_zip(path: &std::path::Path, link_target: &str) {
+    let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+    let opts = zip::write::SimpleFileOptions::default();
+    zip.start_file("demo/SKILL.md", opts).unwrap();
+    zip.write_all(b"---\nname: demo\ndescription: evil\n---\n")
+        .unwrap();
+    zip.add_symlink("demo/esc", link_target, opts).unwrap();
+    zip.start_file("demo/esc/pwned.txt", opts).unwrap();
+    zip.write_all(b"pwned").unwrap();
+    zip.finish().unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn zip_symlinks_cannot_write_outside_the_archive() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let victim = fx.root.join("victim");
+    fs::create_dir_all(&victim).unwrap();
+
+    let absolute = fx.project.join("abs.zip");
+    write_escaping_zip(&absolute, victim.to_str().unwrap());
+    let relative = fx.project.join("rel.zip");
+    write_escaping_zip(&relative, "../../../../../../../../../../..");
+
+    for archive in [&absolute, &relative] {
+        let archive = archive.to_str().unwrap();
+        for args in [vec!["install", archive], vec!["unpack", archive]] {
+            let out = fx.sk_cmd().args(&args).output().unwrap();
+            assert!(
+                !out.status.success(),
+                "{args:?} should refuse an escaping symlink"
+            );
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            assert!(stderr.contains("must point down into"), "{stderr}");
+        }
+    }
+    assert!(!victim.join("pwned.txt").exists());
+
+    // A link that stays inside still can't be written through.
+    let inner = fx.project.join("inner.zip");
+    write_escaping_zip(&inner, ".");
+    let out = fx
+        .sk_cmd()
+        .args(["unpack", inner.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("through the symlink"));
+    assert!(!fx.skill_dir("demo").exists());
+}
+
+#[cfg(unix)]
+fn write_linked_zip(path: &std::path::Path, files: &[(&str, &str)], links: &[(&str, &str)]) {
+    let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+    let opts = zip::write::SimpleFileOptions::default();
+    for (name, contents) in files {
+        zip.start_file(*name, opts).unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+    }
+    for (name, target) in links {
+        zip.add_symlink(*name, *target, opts).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn zip_symlinks_cannot_chain_or_reach_sibling_folders() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let skill_md = "---\nname: demo\ndescription: evil\n---\n";
+
+    // Each link looks harmless against the archive root on its own, but
+    // together they resolve two levels above it.
+    let chained = fx.project.join("chained.zip");
+    write_linked_zip(
+        &chained,
+        &[("demo/SKILL.md", skill_md)],
+        &[("demo/sub/sub2/d", "../.."), ("demo/x", "sub/sub2/d/../..")],
+    );
+    // No `..`, but the second link resolves through the first.
+    let through = fx.project.join("through.zip");
+    write_linked_zip(
+        &through,
+        &[("demo/SKILL.md", skill_md), ("demo/real/file.txt", "ok")],
+        &[("demo/a", "real"), ("demo/b", "a/file.txt")],
+    );
+    // The link stays inside the wrapper folder but leaves the skill.
+    let sibling = fx.project.join("sibling.zip");
+    write_linked_zip(
+        &sibling,
+        &[
+            ("wrapper/demo/SKILL.md", skill_md),
+            ("wrapper/shared/secret.txt", "secret"),
+        ],
+        &[("wrapper/demo/shared", "../shared")],
+    );
+
+    for archive in [&chained, &through, &sibling] {
+        let archive = archive.to_str().unwrap();
+        for args in [vec!["install", archive], vec!["unpack", archive]] {
+            let out = fx.sk_cmd().args(&args).output().unwrap();
+            assert!(!out.status.success(), "{args:?} should refuse the link");
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            assert!(stderr.contains("must point down into"), "{stderr}");
+        }
+    }
+    assert!(!fx.skill_dir("demo").exists());
+}
@@ -0,0 +1,122 @@
+use std::fs;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::CliFixture;
+
+#[test]
+fn sync_back_patch_round_trips_through_apply_patch() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("patch-src", "skills/demo", "demo");
+    fx.install_from_remote(&remote, "demo");
+    let dir = fx.skill_dir("demo");
+    let original = fs::read_to_string(dir.join("file.txt")).unwrap();
+    fs::write(dir.join("file.txt"), "shared edit\n").unwrap();
+    fs::write(dir.join("extra.md"), "new file\n").unwrap();
+
+    let out_path = fx.root.join("out/demo.patch");
+    fx.sk_success(&[
+        "sync-back",
+        "demo",
+        "--patch",
+        out_path.to_str().unwrap(),
+        "--message",
+        "Share demo edits",
+    ]);
+    let patch = fs::read_to_string(&out_path).unwrap();
+    assert!(patch.starts_with("From "), "{patch}");
+    assert!(
+        patch.contains("Subject: [PATCH] Share demo edits"),
+        "{patch}"
+    );
+    assert!(patch.contains("skills/demo/extra.md"), "{patch}");
+    let branches = std::process::Command::new("git")
+        .args(["branch", "--list", "--format=%(refname:short)"])
+        .current_dir(&remote.bare)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&branches.stdout).trim(), "main");
+
+    // A collaborator with a pristine install applies the patch.
+    fs::write(dir.join("file.txt"), &original).unwrap();
+    fs::remove_file(dir.join("extra.md")).unwrap();
+    fs::write(dir.join("local.md"), "kept\n").unwrap();
+    fx.sk_success(&["apply-patch", "demo", out_path.to_str().unwrap()]);
+    assert_eq!(
+        fs::read_to_string(dir.join("file.txt")).unwrap(),
+        "shared edit\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("extra.md")).unwrap(),
+        "new file\n"
+    );
+    assert_eq!(fs::read_to_string(dir.join("local.md")).unwrap(), "kept\n");
+}
+
+#[test]
+fn apply_patch_reports_conflicts_and_leaves_install_alone() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("patch-clash", "skills/demo", "demo");
+    fx.install_from_remote(&remote, "demo");
+    let dir = fx.skill_dir("demo");
+    let original = fs::read_to_string(dir.join("file.txt")).unwrap();
+    fs::write(dir.join("file.txt"), "theirs\n").unwrap();
+    let out_path = fx.root.join("clash.patch");
+    fx.sk_success(&["sync-back", "demo", "--patch", out_path.to_str().unwrap()]);
+
+    fs::write(dir.join("file.txt"), "mine\n").unwrap();
+    let out = fx
+        .sk_cmd()
+        .args(["apply-patch", "demo", out_path.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("skills/demo/file.txt"), "{stderr}");
+    assert_eq!(fs::read_to_string(dir.join("file.txt")).unwrap(), "mine\n");
+    assert_ne!(original, "mine\n");
+}
+
+#[test]
+fn mbox_format_keeps_one_entry_per_skill() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("patch-mbox", "skills/one", "one");
+    remote.overwrite_file(
+        "../two/SKILL.md",
+        "---\nname: two\ndescription: fixture\n---\n",
+        "add two",
+    );
+    fx.install_from_remote(&remote, "one");
+    fx.sk_success(&["install", &remote.file_url(), "two", "--path", "skills/two"]);
+    fs::write(fx.skill_dir("one").join("a.md"), "a\n").unwrap();
+    fs::write(fx.skill_dir("two").join("b.md"), "b\n").unwrap();
+
+    let mbox = fx.root.join("series.mbox");
+    fx.sk_success(&[
+        "sync-back",
+        "one",
+        "two",
+        "--patch",
+        mbox.to_str().unwrap(),
+        "--format",
+        "mbox",
+    ]);
+    let text = fs::read_to_string(&mbox).unwrap();
+    assert!(text.contains("[PATCH 1/2]"), "{text}");
+    assert!(text.contains("[PATCH 2/2]"), "{text}");
+
+    let combined = fx.root.join("combined.patch");
+    fx.sk_success(&[
+        "sync-back",
+        "one",
+        "two",
+        "--patch",
+        combined.to_str().unwrap(),
+    ]);
+    let text = fs::read_to_string(&combined).unwrap();
+    assert_eq!(text.matches("Subject: [PATCH]").count(), 1, "{text}");
+}
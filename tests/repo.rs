@@ -76,6 +76,46 @@ fn repo_search_matches_across_cached_repos() {
     assert_eq!(array[0]["name"], "beta-skill");
 }
 
+#[test]
+fn repo_search_ranks_stemmed_matches() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let alpha = fx.create_remote("alpha-rank", ".", "alpha-skill");
+    let beta = fx.create_remote("beta-rank", ".", "beta_skill");
+
+    fx.sk_success(&["repo", "add", &beta.file_url(), "--alias", "beta"]);
+    fx.sk_success(&["repo", "add", &alpha.file_url(), "--alias", "alpha"]);
+
+    let hits = fx.run_json(&["repo", "search", "alpha skills", "--json"]);
+    let array = hits.as_array().expect("search hits array");
+    assert_eq!(array.len(), 2, "both skills share the stemmed 'skill' term");
+    assert_eq!(array[0]["name"], "alpha-skill");
+    assert_eq!(array[1]["name"], "beta_skill");
+}
+
+#[test]
+fn repo_search_matches_partial_words() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let brand = fx.create_remote(
+        "brand-partial",
+        "skills/brand-guidelines",
+        "brand-guidelines",
+    );
+    let other = fx.create_remote("other-partial", ".", "canvas");
+
+    fx.sk_success(&["repo", "add", &brand.file_url(), "--alias", "brand"]);
+    fx.sk_success(&["repo", "add", &other.file_url(), "--alias", "other"]);
+
+    let hits = fx.run_json(&["repo", "search", "guide", "--json"]);
+    let array = hits.as_array().expect("search hits array");
+    assert_eq!(array.len(), 1, "{hits}");
+    assert_eq!(array[0]["name"], "brand-guidelines");
+
+    let hits = fx.run_json(&["repo", "search", "ills/bra", "--json"]);
+    assert_eq!(hits[0]["path"], "skills/brand-guidelines");
+}
+
 #[test]
 fn repo_search_accepts_repo_flag() {
     let fx = CliFixture::new();
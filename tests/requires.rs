@@ -0,0 +1,128 @@
+#[path = "support/mod.rs"]
+mod support;
+
+use support::CliFixture;
+
+fn lock_names(fx: &CliFixture) -> Vec<String> {
+    let mut names: Vec<String> = fx.lock_json()["skills"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["installName"].as_str().unwrap().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn install_pulls_in_requirements_from_the_same_repo() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("deps-same", "skills/demo", "demo");
+    remote.overwrite_file(
+        "../testing/SKILL.md",
+        "---\nname: testing\ndescription: run tests\nrequires: [linting]\n---\n",
+        "add testing",
+    );
+    remote.overwrite_file(
+        "../linting/SKILL.md",
+        "---\nname: linting\ndescription: run linters\n---\n",
+        "add linting",
+    );
+    remote.overwrite_file(
+        "SKILL.md",
+        "---\nname: demo\ndescription: fixture\nrequires:\n  - testing\n---\n",
+        "demo requires testing",
+    );
+
+    fx.install_from_remote(&remote, "demo");
+
+    assert_eq!(lock_names(&fx), vec!["demo", "linting", "testing"]);
+    assert!(fx.skill_dir("testing").join("SKILL.md").exists());
+    assert!(fx.skill_dir("linting").join("SKILL.md").exists());
+}
+
+#[test]
+fn install_resolves_requirements_from_registered_repos() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let shared = fx.create_remote("deps-shared", "testing", "testing");
+    fx.sk_success(&["repo", "add", &shared.file_url(), "--alias", "shared"]);
+
+    let remote = fx.create_remote("deps-cross", "skills/demo", "demo");
+    remote.overwrite_file(
+        "SKILL.md",
+        "---\nname: demo\ndescription: fixture\nrequires: testing\n---\n",
+        "demo requires testing",
+    );
+    fx.install_from_remote(&remote, "demo");
+
+    assert_eq!(lock_names(&fx), vec!["demo", "testing"]);
+    let skills = fx.lock_json()["skills"].as_array().unwrap().clone();
+    let testing = skills
+        .iter()
+        .find(|s| s["installName"] == "testing")
+        .unwrap();
+    assert_eq!(testing["source"]["skillPath"], "testing");
+}
+
+#[test]
+fn unresolvable_requirement_aborts_before_installing() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("deps-missing", "skills/demo", "demo");
+    remote.overwrite_file(
+        "SKILL.md",
+        "---\nname: demo\ndescription: fixture\nrequires: [nowhere]\n---\n",
+        "demo requires nowhere",
+    );
+
+    let url = remote.file_url();
+    let out = fx
+        .sk_cmd()
+        .args(["install", &url, "demo", "--path", remote.skill_path()])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("'demo' requires 'nowhere'"),
+        "unexpected error: {stderr}"
+    );
+    assert!(!fx.skill_dir("demo").exists());
+}
+
+#[test]
+fn remove_refuses_required_skills_and_doctor_flags_missing_ones() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("deps-remove", "skills/demo", "demo");
+    remote.overwrite_file(
+        "../testing/SKILL.md",
+        "---\nname: testing\ndescription: run tests\n---\n",
+        "add testing",
+    );
+    remote.overwrite_file(
+        "SKILL.md",
+        "---\nname: demo\ndescription: fixture\nrequires: [testing]\n---\n",
+        "demo requires testing",
+    );
+    fx.install_from_remote(&remote, "demo");
+
+    let out = fx.sk_cmd().args(["remove", "testing"]).output().unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("'testing' is required by: demo"),
+        "unexpected error: {stderr}"
+    );
+    assert!(fx.skill_dir("testing").exists());
+
+    fx.sk_success(&["remove", "testing", "--force"]);
+    let out = fx.sk_cmd().args(["doctor", "demo"]).output().unwrap();
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("Missing dependency: requires 'testing'"),
+        "doctor output: {stdout}"
+    );
+}
@@ -0,0 +1,111 @@
+use std::fs;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::CliFixture;
+
+#[test]
+fn restore_rebuilds_all_installs_on_fresh_checkout() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let first = fx.create_remote("restore-a", "skills/alpha", "alpha");
+    let second = fx.create_remote("restore-b", "skills/beta", "beta");
+    fx.install_from_remote(&first, "alpha");
+    fx.install_from_remote(&second, "beta");
+
+    // Simulate a fresh clone on CI: no installs, no cache.
+    fs::remove_dir_all(fx.project.join("skills")).unwrap();
+    fs::remove_dir_all(fx.cache_root()).unwrap();
+
+    let out = fx.sk_cmd().arg("restore").output().unwrap();
+    assert!(
+        out.status.success(),
+        "restore failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("2 restored"), "stdout: {stdout}");
+    for name in ["alpha", "beta"] {
+        assert_eq!(
+            fs::read_to_string(fx.skill_dir(name).join("file.txt")).unwrap(),
+            "v1\n"
+        );
+    }
+
+    let again = fx.sk_cmd().arg("restore").output().unwrap();
+    assert!(again.status.success());
+    assert!(String::from_utf8_lossy(&again.stdout).contains("0 restored, 2 already up to date"));
+}
+
+#[test]
+fn restore_fails_on_digest_mismatch() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("restore-digest", "skills/demo", "demo");
+    fx.install_from_remote(&remote, "demo");
+    fs::remove_dir_all(fx.skill_dir("demo")).unwrap();
+
+    let lock_path = fx.project.join("skills.lock.json");
+    let mut lock = fx.lock_json();
+    lock["skills"][0]["digest"] = serde_json::Value::String("sha256:deadbeef".into());
+    fs::write(&lock_path, serde_json::to_string_pretty(&lock).unwrap()).unwrap();
+
+    let out = fx.sk_cmd().arg("restore").output().unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("digest mismatch"), "stderr: {stderr}");
+    assert!(!fx.skill_dir("demo").exists());
+}
+
+#[test]
+fn restore_refuses_modified_install_without_force() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("restore-modified", "skills/demo", "demo");
+    fx.install_from_remote(&remote, "demo");
+    let file = fx.skill_dir("demo").join("file.txt");
+    fs::write(&file, "local edit\n").unwrap();
+
+    let out = fx.sk_cmd().arg("restore").output().unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("--force"), "stderr: {stderr}");
+    assert_eq!(fs::read_to_string(&file).unwrap(), "local edit\n");
+
+    fx.sk_success(&["restore", "--force"]);
+    assert_eq!(fs::read_to_string(&file).unwrap(), "v1\n");
+}
+
+#[test]
+fn restore_changes_nothing_when_any_skill_fails_verification() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let first = fx.create_remote("restore-atomic-a", "skills/alpha", "alpha");
+    let second = fx.create_remote("restore-atomic-b", "skills/beta", "beta");
+    fx.install_from_remote(&first, "alpha");
+    fx.install_from_remote(&second, "beta");
+    fs::remove_dir_all(fx.project.join("skills")).unwrap();
+
+    let lock_path = fx.project.join("skills.lock.json");
+    let mut lock = fx.lock_json();
+    for skill in lock["skills"].as_array_mut().unwrap() {
+        if skill["installName"] == "beta" {
+            skill["digest"] = serde_json::Value::String("sha256:deadbeef".into());
+        }
+    }
+    fs::write(&lock_path, serde_json::to_string_pretty(&lock).unwrap()).unwrap();
+
+    let out = fx.sk_cmd().arg("restore").output().unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("no installs were changed"),
+        "stderr: {stderr}"
+    );
+    assert!(
+        !fx.skill_dir("alpha").exists(),
+        "alpha must not be restored when beta fails"
+    );
+    assert!(!fx.skill_dir("beta").exists());
+}
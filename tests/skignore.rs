@@ -0,0 +1,174 @@
+use std::fs;
+use std::process::Command;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::{parse_status_entries, CliFixture};
+
+const IGNORE_RULES: &str = "node_modules/\n__pycache__/\n*.log\n";
+
+fn write_build_junk(dir: &std::path::Path) {
+    fs::create_dir_all(dir.join("node_modules/dep")).unwrap();
+    fs::write(
+        dir.join("node_modules/dep/index.js"),
+        "module.exports = 1;\n",
+    )
+    .unwrap();
+    fs::create_dir_all(dir.join("__pycache__")).unwrap();
+    fs::write(dir.join("__pycache__/helper.pyc"), [0u8, 1, 2]).unwrap();
+    fs::write(dir.join("debug.log"), "noise\n").unwrap();
+}
+
+#[test]
+fn ignored_files_do_not_count_as_modifications() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("skignore-digest", "skills/demo", "demo");
+    remote.overwrite_file(".skignore", IGNORE_RULES, "add skignore");
+    fx.install_from_remote(&remote, "demo");
+
+    write_build_junk(&fx.skill_dir("demo"));
+    let status = parse_status_entries(fx.run_json(&["doctor", "--status", "--json"]));
+    assert_eq!(status[0].state, "clean", "{:?}", status[0]);
+
+    fs::write(fx.skill_dir("demo").join("file.txt"), "real edit\n").unwrap();
+    let status = parse_status_entries(fx.run_json(&["doctor", "--status", "--json"]));
+    assert_eq!(status[0].state, "modified");
+}
+
+#[test]
+fn upgrade_keeps_ignored_files_in_place() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("skignore-upgrade", "skills/demo", "demo");
+    remote.overwrite_file(".skignore", IGNORE_RULES, "add skignore");
+    fx.install_from_remote(&remote, "demo");
+    let dir = fx.skill_dir("demo");
+    write_build_junk(&dir);
+
+    remote.overwrite_file("file.txt", "v2\n", "upstream v2");
+    fx.sk_success(&["upgrade", "demo"]);
+
+    assert_eq!(fs::read_to_string(dir.join("file.txt")).unwrap(), "v2\n");
+    assert!(dir.join("node_modules/dep/index.js").exists());
+    assert!(dir.join("__pycache__/helper.pyc").exists());
+    assert!(dir.join("debug.log").exists());
+    let status = parse_status_entries(fx.run_json(&["doctor", "--status", "--json"]));
+    assert_eq!(status[0].state, "clean", "{:?}", status[0]);
+}
+
+#[test]
+fn sync_back_does_not_publish_ignored_files() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let personal = fx.create_remote("skignore-sync", ".", "placeholder");
+    fx.sk_success(&["config", "set", "default_repo", &personal.file_url()]);
+
+    let dir = fx.skill_dir("scripted");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        "---\nname: scripted\ndescription: has helpers\n---\n",
+    )
+    .unwrap();
+    fs::write(dir.join(".skignore"), IGNORE_RULES).unwrap();
+    write_build_junk(&dir);
+
+    let out = fx
+        .sk_cmd()
+        .env("SK_FORCE_GH_MISSING", "1")
+        .env("SK_FORCE_RSYNC_MISSING", "1")
+        .args(["sync-back", "scripted", "--message", "Publish scripted"])
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "sync-back failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let commit = fx.lock_json()["skills"][0]["commit"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let listing = Command::new("git")
+        .arg("--git-dir")
+        .arg(&personal.bare)
+        .args(["ls-tree", "-r", "--name-only", &commit, "scripted"])
+        .output()
+        .unwrap();
+    assert!(listing.status.success());
+    let files = String::from_utf8_lossy(&listing.stdout);
+    let mut published: Vec<&str> = files.lines().collect();
+    published.sort();
+    assert_eq!(
+        published,
+        vec!["scripted/.skignore", "scripted/SKILL.md"],
+        "ignored files leaked upstream"
+    );
+}
+
+/// Publish a fresh `logged` skill with `rules` as its `.skignore` and return
+/// the paths that landed upstream.
+fn publish_logged_skill(rules: &str, force_rsync_missing: bool) -> Vec<String> {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let personal = fx.create_remote("skignore-negate", ".", "placeholder");
+    fx.sk_success(&["config", "set", "default_repo", &personal.file_url()]);
+
+    let dir = fx.skill_dir("logged");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("SKILL.md"),
+        "---\nname: logged\ndescription: keeps one log\n---\n",
+    )
+    .unwrap();
+    fs::write(dir.join(".skignore"), rules).unwrap();
+    fs::write(dir.join("debug.log"), "noise\n").unwrap();
+    fs::write(dir.join("keep.log"), "wanted\n").unwrap();
+
+    let mut cmd = fx.sk_cmd();
+    cmd.env("SK_FORCE_GH_MISSING", "1");
+    if force_rsync_missing {
+        cmd.env("SK_FORCE_RSYNC_MISSING", "1");
+    }
+    let out = cmd
+        .args(["sync-back", "logged", "--message", "Publish logged"])
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "sync-back failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let commit = fx.lock_json()["skills"][0]["commit"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let listing = Command::new("git")
+        .arg("--git-dir")
+        .arg(&personal.bare)
+        .args(["ls-tree", "-r", "--name-only", &commit, "logged"])
+        .output()
+        .unwrap();
+    assert!(listing.status.success());
+    let mut published: Vec<String> = String::from_utf8_lossy(&listing.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+    published.sort();
+    published
+}
+
+#[test]
+fn sync_back_honors_negated_skignore_patterns_with_and_without_rsync() {
+    for force_rsync_missing in [false, true] {
+        assert_eq!(
+            publish_logged_skill("*.log\n!keep.log\n", force_rsync_missing),
+            vec!["logged/.skignore", "logged/SKILL.md", "logged/keep.log"],
+            "force_rsync_missing={force_rsync_missing}"
+        );
+    }
+}
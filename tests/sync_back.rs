@@ -1,4 +1,8 @@
 use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 #[path = "support/mod.rs"]
 mod support;
@@ -490,7 +494,7 @@ fn sync_back_refreshes_local_digest_to_merged_commit() {
     // Upstream changes land after install but before sync-back.
     remote.overwrite_file("file.txt", "remote v2\n", "Upstream edit");
 
-    // Local edits add a new file so the sync-back branch diverges.
+    // Local edits add a new file; --rebase replays them onto the upstream edit.
     let skill_dir = fx.skill_dir("sk-digest");
     fs::create_dir_all(&skill_dir).unwrap();
     fs::write(skill_dir.join("local.txt"), "local content\n").unwrap();
@@ -513,6 +517,7 @@ fn sync_back_refreshes_local_digest_to_merged_commit() {
         .args([
             "sync-back",
             "sk-digest",
+            "--rebase",
             "--branch",
             branch,
             "--message",
@@ -576,3 +581,441 @@ fn sync_back_refreshes_local_digest_to_merged_commit() {
         "lockfile should track merged commit"
     );
 }
+
+/// Minimal stand-in for the Gitea REST API: serves one repo, remembers whether a
+/// PR was opened, and logs every request line it sees. The first page of pulls
+/// is filled with other branches so the PR only shows up on page two.
+fn spawn_fake_gitea(branch: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}/api/v1", listener.local_addr().unwrap());
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let seen = Arc::clone(&log);
+    thread::spawn(move || {
+        let mut created = false;
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            let mut auth = String::new();
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                let lower = header.to_ascii_lowercase();
+                if let Some(v) = lower.strip_prefix("content-length:") {
+                    content_length = v.trim().parse().unwrap();
+                } else if lower.starts_with("authorization:") {
+                    auth = header["authorization:".len()..].trim().to_string();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_string();
+            let path = parts.next().unwrap_or_default().to_string();
+            seen.lock().unwrap().push(format!(
+                "{method} {path} [{auth}] {}",
+                String::from_utf8_lossy(&body)
+            ));
+
+            let pull = format!(
+                r#"{{"number":3,"html_url":"https://gitea.test/pulls/3","state":"open","merged":false,"mergeable":true,"head":{{"ref":"{branch}"}}}}"#
+            );
+            let (status, reply) = if method == "GET" && path.contains("/pulls?") {
+                let reply = if path.contains("page=1") {
+                    let others: Vec<String> = (100..150)
+                        .map(|n| {
+                            format!(
+                                r#"{{"number":{n},"state":"closed","merged":true,"head":{{"ref":"other-{n}"}}}}"#
+                            )
+                        })
+                        .collect();
+                    format!("[{}]", others.join(","))
+                } else if created && path.contains("page=2") {
+                    format!("[{pull}]")
+                } else {
+                    "[]".into()
+                };
+                ("200 OK", reply)
+            } else if method == "POST" && path.ends_with("/pulls") {
+                created = true;
+                ("201 Created", pull.clone())
+            } else if method == "POST" && path.ends_with("/merge") {
+                ("200 OK", String::new())
+            } else if path.ends_with("/pulls/3") {
+                ("200 OK", pull.clone())
+            } else {
+                ("200 OK", r#"{"default_branch":"main"}"#.to_string())
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{reply}",
+                reply.len()
+            );
+        }
+    });
+    (base, log)
+}
+
+#[test]
+fn sync_back_opens_gitea_pr_through_the_api() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let remote = fx.create_remote("skills-gitea", "template", "template-skill");
+    let skill_dir = fx.skill_dir("sk-gitea");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: sk-gitea\ndescription: gitea skill\n---\n",
+    )
+    .unwrap();
+
+    let (api, log) = spawn_fake_gitea("sk/gitea-branch");
+    let repo_url = remote.file_url();
+    let out = fx
+        .sk_cmd()
+        .env("SK_FORGE", "gitea")
+        .env("SK_GITEA_API_URL", &api)
+        .env("SK_GITEA_TOKEN", "s3cret")
+        .env("SK_SYNC_BACK_AUTO_MERGE_TIMEOUT_MS", "0")
+        .env("SK_SYNC_BACK_AUTO_MERGE_POLL_MS", "0")
+        .args([
+            "sync-back",
+            "sk-gitea",
+            "--repo",
+            &repo_url,
+            "--branch",
+            "sk/gitea-branch",
+            "--message",
+            "Publish via Gitea",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(
+        out.status.success(),
+        "sync-back failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = normalize_newlines(&String::from_utf8_lossy(&out.stdout));
+    assert!(
+        stdout.contains("Opened PR https://gitea.test/pulls/3"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("Auto-merge armed; Gitea will land https://gitea.test/pulls/3"),
+        "{stdout}"
+    );
+
+    let log = log.lock().unwrap();
+    let create = log
+        .iter()
+        .find(|line| line.starts_with("POST") && line.contains("/pulls ["))
+        .expect("PR created");
+    assert!(create.contains("[token s3cret]"), "{create}");
+    assert!(create.contains("\"head\":\"sk/gitea-branch\""), "{create}");
+    assert!(create.contains("\"base\":\"main\""), "{create}");
+    assert!(create.contains("Publish via Gitea"), "{create}");
+    assert!(
+        log.iter().any(|line| line.starts_with("GET")
+            && line.contains("/pulls?")
+            && line.contains("page=2")),
+        "{log:?}"
+    );
+    assert!(
+        log.iter()
+            .any(|line| line.contains("/pulls/3/merge")
+                && line.contains("merge_when_checks_succeed")),
+        "{log:?}"
+    );
+}
+
+#[test]
+fn sync_back_skips_gitea_automation_without_token() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+
+    let remote = fx.create_remote("skills-gitea-anon", "template", "template-skill");
+    let skill_dir = fx.skill_dir("sk-anon");
+    fs::create_dir_all(&skill_dir).unwrap();
+    fs::write(
+        skill_dir.join("SKILL.md"),
+        "---\nname: sk-anon\ndescription: gitea skill\n---\n",
+    )
+    .unwrap();
+
+    let repo_url = remote.file_url();
+    let out = fx
+        .sk_cmd()
+        .env("SK_FORGE", "gitea")
+        .env_remove("SK_GITEA_TOKEN")
+        .env_remove("GITEA_TOKEN")
+        .args(["sync-back", "sk-anon", "--repo", &repo_url])
+        .output()
+        .unwrap();
+
+    assert!(out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("no Gitea token is set"), "{stderr}");
+}
+
+#[test]
+fn sync_back_dry_run_prints_patch_without_publishing() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("skills-dry", "skills/dry", "dry");
+    fx.install_from_remote(&remote, "dry");
+    let locked_before = fx.lock_json();
+
+    let skill_dir = fx.skill_dir("dry");
+    fs::write(skill_dir.join("file.txt"), "edited locally\n").unwrap();
+    fs::write(skill_dir.join("NOTES.md"), "new notes\n").unwrap();
+
+    let out = fx
+        .sk_cmd()
+        .env("SK_FORCE_GH_MISSING", "1")
+        .args(["sync-back", "dry", "--dry-run"])
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "dry run failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = normalize_newlines(&String::from_utf8_lossy(&out.stdout));
+    assert!(stdout.contains("Dry run: sync-back of 'dry'"), "{stdout}");
+    assert!(stdout.contains("2 files changed"), "{stdout}");
+    assert!(stdout.contains("+++ b/skills/dry/NOTES.md"), "{stdout}");
+    assert!(stdout.contains("+edited locally"), "{stdout}");
+    assert!(!stdout.contains("Pushed branch"), "{stdout}");
+
+    let branches = std::process::Command::new("git")
+        .args(["branch", "--list"])
+        .current_dir(&remote.bare)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&branches.stdout).trim(), "* main");
+    assert_eq!(fx.lock_json()["skills"], locked_before["skills"]);
+
+    fs::remove_file(skill_dir.join("NOTES.md")).unwrap();
+    let original = fs::read_to_string(remote.work.join("skills/dry/file.txt")).unwrap();
+    fs::write(skill_dir.join("file.txt"), original).unwrap();
+    let out = fx
+        .sk_cmd()
+        .args(["sync-back", "dry", "--dry-run"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stdout).contains("No changes to sync back for 'dry'"));
+}
+
+#[test]
+fn sync_back_all_modified_batches_one_branch_per_repo() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("skills-batch", "skills/alpha", "alpha");
+    let url = remote.file_url();
+    remote.overwrite_file(
+        "../beta/SKILL.md",
+        "---\nname: beta\ndescription: fixture\n---\n",
+        "add beta",
+    );
+    remote.overwrite_file(
+        "../gamma/SKILL.md",
+        "---\nname: gamma\ndescription: fixture\n---\n",
+        "add gamma",
+    );
+    fx.install_from_remote(&remote, "alpha");
+    fx.sk_success(&["install", &url, "gamma", "--path", "skills/gamma"]);
+    // beta is locked at a newer commit than alpha; gamma stays unmodified.
+    remote.overwrite_file("../README.md", "unrelated\n", "touch readme");
+    fx.sk_success(&["install", &url, "beta", "--path", "skills/beta"]);
+
+    fs::write(fx.skill_dir("alpha").join("file.txt"), "alpha edit\n").unwrap();
+    fs::write(fx.skill_dir("beta").join("notes.md"), "beta edit\n").unwrap();
+    let gamma_before = fx.lock_json()["skills"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["installName"] == "gamma")
+        .unwrap()
+        .clone();
+
+    let out = fx
+        .sk_cmd()
+        .env("SK_FORCE_GH_MISSING", "1")
+        .args([
+            "sync-back",
+            "--all-modified",
+            "--branch",
+            "sk/batch",
+            "--message",
+            "Batch edits",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "batch sync-back failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let log = std::process::Command::new("git")
+        .args(["log", "--format=%s", "main..sk/batch"])
+        .current_dir(&remote.bare)
+        .output()
+        .unwrap();
+    let subjects = String::from_utf8_lossy(&log.stdout).to_string();
+    assert_eq!(
+        subjects.lines().collect::<Vec<_>>(),
+        vec!["Batch edits (beta)", "Batch edits (alpha)"]
+    );
+    let head = std::process::Command::new("git")
+        .args(["rev-parse", "sk/batch"])
+        .current_dir(&remote.bare)
+        .output()
+        .unwrap();
+    let head = String::from_utf8_lossy(&head.stdout).trim().to_string();
+
+    let lock = fx.lock_json();
+    let skills = lock["skills"].as_array().unwrap();
+    let find = |name: &str| skills.iter().find(|s| s["installName"] == name).unwrap();
+    assert_eq!(find("alpha")["commit"], head.as_str());
+    assert_eq!(find("beta")["commit"], head.as_str());
+    assert_eq!(find("gamma"), &gamma_before);
+}
+
+#[test]
+fn sync_back_batch_refuses_to_revert_upstream_changes() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("skills-stale", "skills/alpha", "alpha");
+    let url = remote.file_url();
+    remote.overwrite_file(
+        "../beta/SKILL.md",
+        "---\nname: beta\ndescription: fixture\n---\n",
+        "add beta",
+    );
+    fx.install_from_remote(&remote, "alpha");
+    remote.overwrite_file("file.txt", "upstream alpha\n", "upstream alpha change");
+    fx.sk_success(&["install", &url, "beta", "--path", "skills/beta"]);
+
+    let out = fx
+        .sk_cmd()
+        .env("SK_FORCE_GH_MISSING", "1")
+        .args(["sync-back", "alpha", "beta", "--dry-run"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("upstream changed it"), "{stderr}");
+}
+
+fn remote_branches(remote: &support::RemoteRepo) -> String {
+    let out = std::process::Command::new("git")
+        .args(["branch", "--list", "--format=%(refname:short)"])
+        .current_dir(&remote.bare)
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+}
+
+#[test]
+fn sync_back_refuses_when_skill_changed_upstream() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("skills-diverged", "skills/div", "div");
+    fx.install_from_remote(&remote, "div");
+    remote.overwrite_file("file.txt", "upstream edit\n", "upstream edit");
+    fs::write(fx.skill_dir("div").join("notes.md"), "local notes\n").unwrap();
+
+    let out = fx
+        .sk_cmd()
+        .env("SK_FORCE_GH_MISSING", "1")
+        .args(["sync-back", "div"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(
+        stderr.contains("'div' changed upstream between its locked commit"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("--rebase"), "{stderr}");
+    assert_eq!(remote_branches(&remote), "main");
+}
+
+#[test]
+fn sync_back_rebase_replays_edits_onto_upstream_tip() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("skills-rebase", "skills/reb", "reb");
+    fx.install_from_remote(&remote, "reb");
+    let tip = remote.overwrite_file("file.txt", "upstream edit\n", "upstream edit");
+    fs::write(fx.skill_dir("reb").join("notes.md"), "local notes\n").unwrap();
+
+    let out = fx
+        .sk_cmd()
+        .env("SK_FORCE_GH_MISSING", "1")
+        .args(["sync-back", "reb", "--rebase", "--branch", "sk/reb"])
+        .output()
+        .unwrap();
+    assert!(
+        out.status.success(),
+        "rebase sync-back failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        stdout.contains("Rebased local edits onto origin/main"),
+        "{stdout}"
+    );
+
+    let parent = std::process::Command::new("git")
+        .args(["rev-parse", "sk/reb~1"])
+        .current_dir(&remote.bare)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&parent.stdout).trim(), tip);
+    let dir = fx.skill_dir("reb");
+    assert_eq!(
+        fs::read_to_string(dir.join("file.txt")).unwrap(),
+        "upstream edit\n"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.join("notes.md")).unwrap(),
+        "local notes\n"
+    );
+}
+
+#[test]
+fn sync_back_rebase_reports_conflicts_without_pushing() {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("skills-clash", "skills/clash", "clash");
+    fx.install_from_remote(&remote, "clash");
+    remote.overwrite_file("file.txt", "upstream edit\n", "upstream edit");
+    fs::write(fx.skill_dir("clash").join("file.txt"), "local edit\n").unwrap();
+
+    let out = fx
+        .sk_cmd()
+        .env("SK_FORCE_GH_MISSING", "1")
+        .args(["sync-back", "clash", "--rebase"])
+        .output()
+        .unwrap();
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("conflicts in:"), "{stderr}");
+    assert!(stderr.contains("skills/clash/file.txt"), "{stderr}");
+    assert_eq!(remote_branches(&remote), "main");
+    assert_eq!(
+        fs::read_to_string(fx.skill_dir("clash").join("file.txt")).unwrap(),
+        "local edit\n"
+    );
+}
@@ -0,0 +1,131 @@
+use std::fs;
+
+#[path = "support/mod.rs"]
+mod support;
+
+use support::{git, parse_status_entries, CliFixture};
+
+const BASE: &str = "alpha\nbeta\ngamma\ndelta\nepsilon\n";
+
+fn setup() -> (CliFixture, support::RemoteRepo) {
+    let fx = CliFixture::new();
+    fx.sk_success(&["init"]);
+    let remote = fx.create_remote("merge", "skills/demo", "demo");
+    remote.overwrite_file("notes.txt", BASE, "add notes");
+    fx.install_from_remote(&remote, "demo");
+    (fx, remote)
+}
+
+fn upgrade_merge(fx: &CliFixture, extra: &str) -> std::process::Output {
+    fx.sk_cmd()
+        .args(["upgrade", "demo", extra])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn merge_applies_non_overlapping_upstream_changes() {
+    let (fx, remote) = setup();
+    let notes = fx.skill_dir("demo").join("notes.txt");
+    fs::write(&notes, "alpha\nbeta (local)\ngamma\ndelta\nepsilon\n").unwrap();
+    let upstream = remote.overwrite_file(
+        "notes.txt",
+        "alpha\nbeta\ngamma\ndelta\nepsilon (upstream)\n",
+        "upstream edit",
+    );
+    fs::write(remote.work.join("skills/demo/new.txt"), "added upstream\n").unwrap();
+    git(&["add", "."], &remote.work);
+    git(&["commit", "-m", "add file"], &remote.work);
+    git(&["push", "origin", "main"], &remote.work);
+    let tip = remote.head();
+    assert_ne!(upstream, tip);
+
+    let out = upgrade_merge(&fx, "--merge");
+    assert!(
+        out.status.success(),
+        "merge failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(&notes).unwrap(),
+        "alpha\nbeta (local)\ngamma\ndelta\nepsilon (upstream)\n"
+    );
+    assert_eq!(
+        fs::read_to_string(fx.skill_dir("demo").join("new.txt")).unwrap(),
+        "added upstream\n"
+    );
+    let lock = fx.lock_json();
+    assert_eq!(lock["skills"][0]["commit"].as_str(), Some(tip.as_str()));
+
+    // The install still carries local edits relative to upstream.
+    let status = parse_status_entries(fx.run_json(&["doctor", "--status", "--json"]));
+    assert_eq!(status[0].state, "modified");
+    assert!(status[0].update.is_none());
+}
+
+#[test]
+fn merge_conflicts_block_lock_update_until_continue() {
+    let (fx, remote) = setup();
+    let locked = fx.lock_json()["skills"][0]["commit"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let notes = fx.skill_dir("demo").join("notes.txt");
+    fs::write(&notes, "alpha\nbeta (local)\ngamma\ndelta\nepsilon\n").unwrap();
+    let tip = remote.overwrite_file(
+        "notes.txt",
+        "alpha\nbeta (upstream)\ngamma\ndelta\nepsilon\n",
+        "conflicting edit",
+    );
+
+    let out = upgrade_merge(&fx, "--merge");
+    assert!(!out.status.success());
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(stdout.contains("notes.txt (conflict markers)"), "{stdout}");
+    let merged = fs::read_to_string(&notes).unwrap();
+    assert!(merged.contains("<<<<<<< local"), "{merged}");
+    assert!(merged.contains(">>>>>>> upstream"), "{merged}");
+    assert_eq!(
+        fx.lock_json()["skills"][0]["commit"].as_str(),
+        Some(locked.as_str())
+    );
+
+    let early = upgrade_merge(&fx, "--continue");
+    assert!(!early.status.success());
+    assert!(String::from_utf8_lossy(&early.stderr).contains("still conflicted"));
+
+    fs::write(&notes, "alpha\nbeta (both)\ngamma\ndelta\nepsilon\n").unwrap();
+    let done = upgrade_merge(&fx, "--continue");
+    assert!(
+        done.status.success(),
+        "continue failed: {}",
+        String::from_utf8_lossy(&done.stderr)
+    );
+    assert_eq!(
+        fx.lock_json()["skills"][0]["commit"].as_str(),
+        Some(tip.as_str())
+    );
+}
+
+#[test]
+fn merge_abort_restores_local_files() {
+    let (fx, remote) = setup();
+    let notes = fx.skill_dir("demo").join("notes.txt");
+    let local = "alpha\nbeta (local)\ngamma\ndelta\nepsilon\n";
+    fs::write(&notes, local).unwrap();
+    remote.overwrite_file(
+        "notes.txt",
+        "alpha\nbeta (upstream)\ngamma\ndelta\nepsilon\n",
+        "conflicting edit",
+    );
+
+    assert!(!upgrade_merge(&fx, "--merge").status.success());
+    let again = upgrade_merge(&fx, "--merge");
+    assert!(!again.status.success());
+    assert!(String::from_utf8_lossy(&again.stderr).contains("already in progress"));
+
+    fx.sk_success(&["upgrade", "demo", "--abort"]);
+    assert_eq!(fs::read_to_string(&notes).unwrap(), local);
+    let missing = upgrade_merge(&fx, "--continue");
+    assert!(String::from_utf8_lossy(&missing.stderr).contains("no merge in progress"));
+}